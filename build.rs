@@ -0,0 +1,32 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+#[path = "src/manifest.rs"]
+mod manifest;
+#[path = "src/part.rs"]
+mod part;
+
+/// Generates a test for every entry in the example manifest.
+fn main() {
+    println!("cargo:rerun-if-changed={}", manifest::MANIFEST_PATH);
+    println!("cargo:rerun-if-changed=src/manifest.rs");
+    println!("cargo:rerun-if-changed=src/part.rs");
+    let contents = std::fs::read_to_string(manifest::MANIFEST_PATH).unwrap_or_default();
+    let entries = manifest::parse(&contents)
+        .unwrap_or_else(|err| panic!("{}: {err}", manifest::MANIFEST_PATH));
+    let mut tests = String::new();
+    for entry in entries {
+        writeln!(
+            tests,
+            "#[test]\nfn {}() {{\n    super::assert_example({}, crate::Part::{:?}, {:?}, {:?});\n}}\n",
+            entry.name(),
+            entry.day,
+            entry.part,
+            entry.file,
+            entry.expected
+        )
+        .unwrap();
+    }
+    let out = PathBuf::from(std::env::var_os("OUT_DIR").unwrap()).join("example_tests.rs");
+    std::fs::write(out, tests).unwrap();
+}
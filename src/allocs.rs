@@ -0,0 +1,132 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Whether the counting allocator is installed, via the `count-allocations` feature.
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting every allocation. Counts are process-wide, so they include
+/// rayon worker threads and anything else running at the same time.
+pub struct Counting;
+
+fn allocated(size: usize) {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    /// Counted as allocating a new block of `new_size` and freeing the old one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            allocated(new_size);
+        }
+        new
+    }
+}
+
+/// What a phase allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub count: usize,
+    pub bytes: usize,
+    /// The most memory the phase had allocated at once, beyond what was live when it started.
+    pub peak: usize,
+}
+
+fn bytes(f: &mut fmt::Formatter<'_>, bytes: usize) -> fmt::Result {
+    match bytes {
+        0..=1023 => write!(f, "{bytes}B"),
+        1024..=1048575 => write!(f, "{:.1}KiB", bytes as f64 / 1024.0),
+        _ => write!(f, "{:.1}MiB", bytes as f64 / 1048576.0),
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} allocations, ", self.count)?;
+        bytes(f, self.bytes)?;
+        write!(f, " allocated, ")?;
+        bytes(f, self.peak)?;
+        write!(f, " peak")
+    }
+}
+
+/// Runs `f`, counting what it allocates when the counting allocator is installed.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !ENABLED {
+        return (f(), None);
+    }
+    let count = COUNT.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let result = f();
+    let stats = AllocStats {
+        count: COUNT.load(Ordering::Relaxed) - count,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (result, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    #[test]
+    fn display_test() {
+        let stats = AllocStats {
+            count: 3,
+            bytes: 2048,
+            peak: 100,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 allocations, 2.0KiB allocated, 100B peak"
+        );
+    }
+
+    #[test]
+    fn measure_test() {
+        let (sum, stats) = measure(|| black_box(vec![1u64; 1000]).iter().sum::<u64>());
+        assert_eq!(sum, 1000);
+        assert_eq!(stats.is_some(), ENABLED);
+        // Other tests allocate at the same time, so these are lower bounds
+        if let Some(stats) = stats {
+            assert!(stats.count >= 1);
+            assert!(stats.bytes >= 8000);
+            assert!(stats.peak >= 8000);
+        }
+    }
+}
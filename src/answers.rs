@@ -0,0 +1,182 @@
+use std::fmt::{self, Display};
+use std::io;
+use std::path::PathBuf;
+
+use crate::input::{self, InputSource};
+use crate::{DayResult, Part, RunOptions, Solution};
+
+/// Overrides the directory known answers are read from.
+pub const ANSWERS_DIR_VAR: &str = "AOC_ANSWERS_DIR";
+const DEFAULT_ANSWERS_DIR: &str = "answers";
+
+/// Known correct answers for a day's puzzle input, read from `answers/dayN.toml`:
+///
+/// ```toml
+/// part1 = 6440
+/// part2 = "5905"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Whether a part's answer matched the known answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect { expected: String },
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "✅"),
+            Verdict::Incorrect { expected } => write!(f, "❌ expected {expected}"),
+        }
+    }
+}
+
+impl Answers {
+    /// Parses the `key = value` subset of TOML the answer files use.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut answers = Answers::default();
+        for (number, key, value) in key_values(input)? {
+            match key {
+                "part1" => answers.part1 = Some(value.to_string()),
+                "part2" => answers.part2 = Some(value.to_string()),
+                key => return Err(format!("line {number}: unknown key '{key}'")),
+            }
+        }
+        Ok(answers)
+    }
+
+    /// Loads the answers for `day`, or `None` if there is no answers file.
+    pub fn load(day: u8) -> Result<Option<Self>, String> {
+        let path = path(day);
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => Answers::parse(&contents)
+                .map(Some)
+                .map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("could not read {}: {err}", path.display())),
+        }
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    pub fn check(&self, part: Part, answer: &str) -> Option<Verdict> {
+        self.get(part).map(|expected| match expected == answer {
+            true => Verdict::Correct,
+            false => Verdict::Incorrect {
+                expected: expected.to_string(),
+            },
+        })
+    }
+
+    /// Sets the verdict of every part in `result` that has a known answer.
+    pub fn verify(&self, result: &mut DayResult) {
+        for part in result.parts.iter_mut() {
+            if let Ok(answer) = &part.outcome {
+                part.verdict = self.check(part.part, answer);
+            }
+        }
+    }
+}
+
+/// Reads `key = value` lines with optional quotes and `#` comments, as `(line number, key, value)`.
+pub(crate) fn key_values(input: &str) -> Result<Vec<(usize, &str, &str)>, String> {
+    let mut pairs = Vec::new();
+    for (number, line) in input.lines().enumerate() {
+        let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(format!("line {}: expected `key = value`", number + 1))?;
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(value);
+        pairs.push((number + 1, key.trim(), value));
+    }
+    Ok(pairs)
+}
+
+pub fn answers_dir() -> PathBuf {
+    std::env::var_os(ANSWERS_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_ANSWERS_DIR))
+}
+
+pub fn path(day: u8) -> PathBuf {
+    answers_dir().join(format!("day{day}.toml"))
+}
+
+/// Checks `S` against its known answers. Skipped when the answers file or the input is missing.
+pub fn regression_test<S: Solution>() {
+    let Some(answers) = Answers::load(S::DAY).unwrap() else {
+        return;
+    };
+    let input = match input::load(S::DAY, &InputSource::Default) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("skipping day {} regression test: {err}", S::DAY);
+            return;
+        }
+    };
+    let result = crate::solve_puzzles::<S>(&input, &RunOptions::default());
+    for part in &result.parts {
+        if let Some(expected) = answers.get(part.part) {
+            assert_eq!(
+                part.outcome.as_deref(),
+                Ok(expected),
+                "day {} part {}",
+                S::DAY,
+                part.part
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let answers = Answers::parse("# my input\npart1 = 6440\npart2 = \"5905\" # checked\n\n");
+        assert_eq!(
+            answers,
+            Ok(Answers {
+                part1: Some("6440".to_string()),
+                part2: Some("5905".to_string()),
+            })
+        );
+        assert!(Answers::parse("part3 = 1").is_err());
+        assert!(Answers::parse("part1").is_err());
+    }
+
+    #[test]
+    fn check_test() {
+        let answers = Answers {
+            part1: Some("6440".to_string()),
+            part2: None,
+        };
+        assert_eq!(answers.check(Part::One, "6440"), Some(Verdict::Correct));
+        assert_eq!(
+            answers.check(Part::One, "6441"),
+            Some(Verdict::Incorrect {
+                expected: "6440".to_string()
+            })
+        );
+        assert_eq!(answers.check(Part::Two, "5905"), None);
+    }
+}
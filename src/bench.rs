@@ -0,0 +1,226 @@
+use std::fmt::{self, Display};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How many times a phase is repeated in `--bench` mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Untimed runs before sampling starts. Cut short once they exceed `budget`.
+    pub warmup: usize,
+    /// A fixed number of timed runs. When `None`, runs repeat until `budget` is spent.
+    pub runs: Option<usize>,
+    pub budget: Duration,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: 3,
+            runs: None,
+            budget: Duration::from_secs(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        samples.sort();
+        let runs = samples.len();
+        let mean = samples.iter().sum::<Duration>() / runs as u32;
+        let variance = match runs {
+            1 => 0.0,
+            _ => {
+                samples
+                    .iter()
+                    .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                    .sum::<f64>()
+                    / (runs - 1) as f64
+            }
+        };
+        let median = match runs % 2 {
+            0 => (samples[runs / 2 - 1] + samples[runs / 2]) / 2,
+            _ => samples[runs / 2],
+        };
+        // Nearest-rank percentile
+        let p95 = samples[(runs * 95).div_ceil(100) - 1];
+        Stats {
+            runs,
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            p95,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:.2?} median (min {:.2?}, mean {:.2?} ± {:.2?}, p95 {:.2?}, {} runs)",
+            self.median, self.min, self.mean, self.stddev, self.p95, self.runs
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InconsistentAnswer {
+    pub expected: String,
+    pub run: usize,
+    pub found: String,
+}
+
+impl Display for InconsistentAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "answer changed between runs: run 1 gave {}, run {} gave {}",
+            self.expected, self.run, self.found
+        )
+    }
+}
+
+impl std::error::Error for InconsistentAnswer {}
+
+fn sample<T, E>(
+    config: &BenchConfig,
+    mut f: impl FnMut() -> T,
+    mut inspect: impl FnMut(usize, T) -> Result<(), E>,
+) -> Result<Stats, E> {
+    let warmup_start = Instant::now();
+    for _ in 0..config.warmup {
+        black_box(f());
+        if warmup_start.elapsed() > config.budget {
+            break;
+        }
+    }
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    loop {
+        let timer = Instant::now();
+        let result = black_box(f());
+        samples.push(timer.elapsed());
+        inspect(samples.len(), result)?;
+        let done = match config.runs {
+            Some(runs) => samples.len() >= runs.max(1),
+            None => start.elapsed() >= config.budget,
+        };
+        if done {
+            break;
+        }
+    }
+    Ok(Stats::from_samples(&mut samples))
+}
+
+/// Repeatedly times `f`, discarding its results.
+pub fn bench<T>(config: &BenchConfig, f: impl FnMut() -> T) -> Stats {
+    sample(config, f, |_, _| Ok::<_, ()>(())).unwrap()
+}
+
+/// Repeatedly times `f`, checking every run produces the same answer.
+pub fn bench_answer<T: Display>(
+    config: &BenchConfig,
+    f: impl FnMut() -> T,
+) -> Result<(String, Stats), InconsistentAnswer> {
+    let mut first: Option<String> = None;
+    let stats = sample(config, f, |run, answer| {
+        let answer = answer.to_string();
+        match &first {
+            None => first = Some(answer),
+            Some(expected) if *expected != answer => {
+                return Err(InconsistentAnswer {
+                    expected: expected.clone(),
+                    run,
+                    found: answer,
+                })
+            }
+            Some(_) => {}
+        }
+        Ok(())
+    })?;
+    Ok((first.unwrap(), stats))
+}
+
+/// Parses a duration such as `500ms`, `2s` or `250` (milliseconds).
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    if let Some(ms) = input.strip_suffix("ms") {
+        return ms.parse().ok().map(Duration::from_millis);
+    }
+    if let Some(secs) = input.strip_suffix('s') {
+        // Rejects negative, infinite and NaN seconds, which `from_secs_f64` would panic on
+        return Duration::try_from_secs_f64(secs.parse().ok()?).ok();
+    }
+    input.parse().ok().map(Duration::from_millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn stats_test() {
+        let stats = Stats::from_samples(&mut millis(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.stddev.as_micros(), 1581);
+    }
+
+    #[test]
+    fn stats_even_median_test() {
+        let stats = Stats::from_samples(&mut millis(&[4, 1, 3, 2]));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1290);
+    }
+
+    #[test]
+    fn bench_answer_test() {
+        let config = BenchConfig {
+            warmup: 0,
+            runs: Some(10),
+            ..Default::default()
+        };
+        let (answer, stats) = bench_answer(&config, || 42).unwrap();
+        assert_eq!(answer, "42");
+        assert_eq!(stats.runs, 10);
+
+        let mut calls = 0;
+        let err = bench_answer(&config, || {
+            calls += 1;
+            calls / 4
+        })
+        .unwrap_err();
+        assert_eq!(err.run, 4);
+        assert_eq!(err.found, "1");
+    }
+
+    #[test]
+    fn parse_duration_test() {
+        assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2s"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_duration("250"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_duration("soon"), None);
+        assert_eq!(parse_duration("-1s"), None);
+        assert_eq!(parse_duration("infs"), None);
+        assert_eq!(parse_duration("NaNs"), None);
+    }
+}
@@ -0,0 +1,203 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use crate::Error;
+
+thread_local! {
+    /// The token for the part running on this thread.
+    static CURRENT: RefCell<Token> = RefCell::new(Token::default());
+}
+
+/// Tells a long-running solver that its part has run out of time. Solvers check it inside long
+/// loops and give up with [`Error::Cancelled`]; a solver that never checks runs to the end.
+#[derive(Debug, Clone, Default)]
+pub struct Token {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Token {
+    pub fn new() -> Self {
+        Token::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Fails once cancelled, so a loop can give up with `?`.
+    pub fn check(&self) -> Result<(), Error> {
+        match self.is_cancelled() {
+            true => Err(Error::Cancelled),
+            false => Ok(()),
+        }
+    }
+}
+
+/// The token for the part running on this thread. Work handed to other threads, such as a rayon
+/// iterator, should take a clone of it first.
+pub fn token() -> Token {
+    CURRENT.with_borrow(Token::clone)
+}
+
+/// Checks the token for the part running on this thread.
+pub fn check() -> Result<(), Error> {
+    CURRENT.with_borrow(Token::check)
+}
+
+/// Puts the previous token back when a run finishes, even by unwinding.
+struct Restore(Option<Token>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            CURRENT.set(previous);
+        }
+    }
+}
+
+/// Gives each run a new token and cancels it once the run passes a timeout. One thread watches
+/// every run, so repeated runs, as when benchmarking, don't each start one.
+pub struct Watchdog {
+    /// Sends each run's token as it starts, then `None` when it finishes.
+    runs: Option<mpsc::Sender<Option<Token>>>,
+}
+
+/// Tells the watchdog a run has finished, even by unwinding.
+struct Finished<'a>(&'a mpsc::Sender<Option<Token>>);
+
+impl Drop for Finished<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.send(None);
+    }
+}
+
+impl Watchdog {
+    /// A watchdog that never cancels anything if there's no `timeout`.
+    pub fn new(timeout: Option<Duration>) -> Self {
+        let runs = timeout.map(|timeout| {
+            let (runs, events) = mpsc::channel::<Option<Token>>();
+            // Stops once the watchdog is dropped and the channel disconnects
+            thread::spawn(move || {
+                while let Ok(Some(token)) = events.recv() {
+                    match events.recv_timeout(timeout) {
+                        Ok(_) => {}
+                        Err(mpsc::RecvTimeoutError::Timeout) => {
+                            token.cancel();
+                            if events.recv().is_err() {
+                                return;
+                            }
+                        }
+                        Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+            });
+            runs
+        });
+        Watchdog { runs }
+    }
+
+    /// Runs `f` with a new token, which is cancelled if `f` runs for longer than the timeout.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let token = Token::new();
+        let _restore = Restore(Some(CURRENT.replace(token.clone())));
+        let _finished = self.runs.as_ref().map(|runs| {
+            let _ = runs.send(Some(token));
+            Finished(runs)
+        });
+        f()
+    }
+}
+
+/// Runs `f` with a new token, which is cancelled once `timeout` passes, if there is one.
+pub fn with_timeout<T>(timeout: Option<Duration>, f: impl FnOnce() -> T) -> T {
+    Watchdog::new(timeout).run(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::BenchConfig;
+    use crate::{solve_part, Part, RunOptions};
+    use std::time::Instant;
+
+    #[test]
+    fn token_test() {
+        let token = Token::new();
+        let clone = token.clone();
+        assert_eq!(clone.check(), Ok(()));
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert_eq!(clone.check(), Err(Error::Cancelled));
+    }
+
+    #[test]
+    fn with_timeout_test() {
+        let start = Instant::now();
+        let result = with_timeout(Some(Duration::from_millis(20)), || -> Result<(), Error> {
+            loop {
+                check()?;
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+        assert_eq!(result, Err(Error::Cancelled));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        // The token is only for the part it was made for
+        assert_eq!(check(), Ok(()));
+        assert_eq!(with_timeout(None, || check().map(|_| 1)), Ok(1));
+        let token = with_timeout(Some(Duration::from_secs(10)), token);
+        assert!(!token.is_cancelled());
+    }
+
+    /// Each run gets its own token and its own time limit.
+    #[test]
+    fn watchdog_test() {
+        let watchdog = Watchdog::new(Some(Duration::from_millis(20)));
+        let wait = || -> Result<(), Error> {
+            loop {
+                check()?;
+                thread::sleep(Duration::from_millis(1));
+            }
+        };
+        for _ in 0..3 {
+            assert_eq!(watchdog.run(check), Ok(()));
+            assert_eq!(watchdog.run(wait), Err(Error::Cancelled));
+        }
+        let quick = watchdog.run(token);
+        thread::sleep(Duration::from_millis(40));
+        assert!(!quick.is_cancelled());
+    }
+
+    /// A part that only gets slow after its first run still times out when benchmarked.
+    #[test]
+    fn bench_timeout_test() {
+        let limit = Duration::from_millis(20);
+        let options = RunOptions {
+            bench: Some(BenchConfig {
+                warmup: 0,
+                runs: Some(3),
+                budget: Duration::from_secs(1),
+            }),
+            timeout: Some(limit),
+            ..RunOptions::default()
+        };
+        let mut runs = 0;
+        let result = solve_part(&options, Part::One, || -> Result<u32, Error> {
+            runs += 1;
+            if runs > 1 {
+                loop {
+                    check()?;
+                    thread::sleep(Duration::from_millis(1));
+                }
+            }
+            Ok(1)
+        });
+        assert_eq!(result.outcome, Err(Error::TimedOut(limit)));
+    }
+}
@@ -0,0 +1,426 @@
+use std::fmt::{self, Display};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::answers::key_values;
+use crate::Part;
+
+/// Overrides the config file the session token and base URL are read from.
+pub const CONFIG_PATH_VAR: &str = "AOC_CONFIG";
+/// Overrides the session token in the config file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Overrides the base URL in the config file, e.g. to point at a mock server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+const DEFAULT_CONFIG_PATH: &str = "aoc.toml";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u16 = 2023;
+const USER_AGENT: &str = "github.com/thomaskendrick/aoc_2023 by tom@tkendrick.com";
+
+/// Settings for talking to the Advent of Code site, read from `aoc.toml`:
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "http://localhost:8080"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+impl Config {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut config = Config::default();
+        for (number, key, value) in key_values(input)? {
+            match key {
+                "session" => config.session = Some(value.to_string()),
+                "base_url" => config.base_url = Some(value.to_string()),
+                key => return Err(format!("line {number}: unknown key '{key}'")),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Reads the config file if there is one, then applies any environment overrides.
+    pub fn load() -> Result<Self, String> {
+        let path = std::env::var_os(CONFIG_PATH_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+        let mut config = match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                Config::parse(&contents).map_err(|err| format!("{}: {err}", path.display()))?
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(err) => return Err(format!("could not read {}: {err}", path.display())),
+        };
+        if let Ok(session) = std::env::var(SESSION_VAR) {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = std::env::var(BASE_URL_VAR) {
+            config.base_url = Some(base_url);
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    Config(String),
+    MissingSession,
+    Status {
+        url: String,
+        status: u16,
+        body: String,
+    },
+    Transport {
+        url: String,
+        message: String,
+    },
+    Write {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Config(message) => write!(f, "{message}"),
+            ClientError::MissingSession => write!(
+                f,
+                "no session token: set `session` in {DEFAULT_CONFIG_PATH} or {SESSION_VAR}"
+            ),
+            ClientError::Status { url, status, body } => {
+                write!(f, "{url} returned {status}: {}", body.trim())
+            }
+            ClientError::Transport { url, message } => {
+                write!(f, "could not reach {url}: {message}")
+            }
+            ClientError::Write { path, source } => {
+                write!(f, "could not write {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently. `wait` is the time left, as the site words it.
+    RateLimited {
+        wait: Option<String>,
+    },
+    /// A response we don't recognise, as plain text.
+    Unrecognised(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, {wait} left to wait")
+            }
+            Outcome::RateLimited { wait: None } => write!(f, "rate limited"),
+            Outcome::Unrecognised(text) => write!(f, "unrecognised response: {text}"),
+        }
+    }
+}
+
+/// The text of the `<article>` in a response page, with tags removed and whitespace collapsed.
+fn article_text(html: &str) -> String {
+    let article = html
+        .find("<article")
+        .and_then(|start| {
+            let end = html[start..].find("</article>")?;
+            Some(&html[start..start + end])
+        })
+        .unwrap_or(html);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Works out the outcome of a submission from the page the site responds with.
+pub fn parse_outcome(html: &str) -> Outcome {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("You gave an answer too recently") {
+        let wait = text
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(wait, _)| wait.to_string());
+        Outcome::RateLimited { wait }
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else {
+        Outcome::Unrecognised(text)
+    }
+}
+
+/// Fetches puzzle inputs and submits answers for one session.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self, ClientError> {
+        let session = config
+            .session
+            .as_deref()
+            .ok_or(ClientError::MissingSession)?;
+        let base_url = config.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL);
+        Ok(Client::new(base_url, session))
+    }
+
+    /// A client for the session in the config file or environment.
+    pub fn from_env() -> Result<Self, ClientError> {
+        Client::from_config(&Config::load().map_err(ClientError::Config)?)
+    }
+
+    fn url(&self, day: u8, page: &str) -> String {
+        format!("{}/{YEAR}/day/{day}/{page}", self.base_url)
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.agent
+            .request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    /// The body of a successful response, or an error describing why there isn't one.
+    fn body(
+        url: String,
+        response: Result<ureq::Response, ureq::Error>,
+    ) -> Result<String, ClientError> {
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, response)) => {
+                return Err(ClientError::Status {
+                    url,
+                    status,
+                    body: response.into_string().unwrap_or_default(),
+                })
+            }
+            Err(err) => {
+                return Err(ClientError::Transport {
+                    url,
+                    message: err.to_string(),
+                })
+            }
+        };
+        response
+            .into_string()
+            .map_err(|err| ClientError::Transport {
+                url,
+                message: err.to_string(),
+            })
+    }
+
+    /// Downloads the puzzle input for `day`.
+    pub fn fetch_input(&self, day: u8) -> Result<String, ClientError> {
+        let url = self.url(day, "input");
+        let response = self.request("GET", &url).call();
+        Client::body(url, response)
+    }
+
+    /// Returns the input cached at `path`, downloading and saving it first if it isn't there.
+    pub fn cached_input(&self, day: u8, path: &Path) -> Result<String, ClientError> {
+        if let Ok(input) = std::fs::read_to_string(path) {
+            return Ok(input);
+        }
+        let input = self.fetch_input(day)?;
+        let write = || {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(path, &input)
+        };
+        write().map_err(|source| ClientError::Write {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(input)
+    }
+
+    /// Submits `answer` for one part of `day`.
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Outcome, ClientError> {
+        let url = self.url(day, "answer");
+        let response = self
+            .request("POST", &url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        let page = Client::body(url, response)?;
+        Ok(parse_outcome(&page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serves one canned response per connection and hands back the requests it received.
+    fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    const WRONG_TOO_HIGH: &str = "<main>\n<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2023/about\">about page</a>. Please wait one minute before trying again. <a href=\"/2023/day/7\">[Return to Day 7]</a></p></article>\n</main>";
+
+    #[test]
+    fn parse_outcome_test() {
+        assert_eq!(
+            parse_outcome("<article><p>That's the right answer!  You are <em>one gold star</em> closer.</p></article>"),
+            Outcome::Correct
+        );
+        assert_eq!(parse_outcome(WRONG_TOO_HIGH), Outcome::TooHigh);
+        assert_eq!(
+            parse_outcome(
+                "<article><p>That's not the right answer; your answer is too low.</p></article>"
+            ),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse_outcome(
+                "<article><p>That's not the right answer.  If you're stuck...</p></article>"
+            ),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            parse_outcome("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 52s left to wait. <a href=\"/2023/day/7\">[Return to Day 7]</a></p></article>"),
+            Outcome::RateLimited {
+                wait: Some("4m 52s".to_string())
+            }
+        );
+        assert_eq!(
+            parse_outcome(
+                "<article><p>You don't seem to be solving the <em>right level</em>.</p></article>"
+            ),
+            Outcome::Unrecognised("You don't seem to be solving the right level .".to_string())
+        );
+    }
+
+    #[test]
+    fn config_test() {
+        let config = Config::parse(
+            "session = \"abc123\" # from the browser\nbase_url = http://localhost:8080\n",
+        );
+        assert_eq!(
+            config,
+            Ok(Config {
+                session: Some("abc123".to_string()),
+                base_url: Some("http://localhost:8080".to_string()),
+            })
+        );
+        assert!(Config::parse("token = abc").is_err());
+        assert!(matches!(
+            Client::from_config(&Config::default()),
+            Err(ClientError::MissingSession)
+        ));
+    }
+
+    #[test]
+    fn fetch_and_cache_test() {
+        let (url, server) = mock_server(vec![(200, "1 2 3\n")]);
+        let client = Client::new(&format!("{url}/"), "abc123");
+        let path = std::env::temp_dir()
+            .join(format!("aoc-client-{}", std::process::id()))
+            .join("day9.txt");
+        assert_eq!(client.cached_input(9, &path).unwrap(), "1 2 3\n");
+        // Served from the cache, so the mock server isn't asked again
+        assert_eq!(client.cached_input(9, &path).unwrap(), "1 2 3\n");
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2023/day/9/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc123\r\n"));
+    }
+
+    #[test]
+    fn submit_test() {
+        let (url, server) = mock_server(vec![(200, WRONG_TOO_HIGH), (400, "Bad session")]);
+        let client = Client::new(&url, "abc123");
+        assert_eq!(
+            client.submit(7, Part::Two, "250665248").unwrap(),
+            Outcome::TooHigh
+        );
+        let err = client.submit(7, Part::Two, "1").unwrap_err();
+        assert!(matches!(err, ClientError::Status { status: 400, .. }));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/7/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=250665248"));
+    }
+}
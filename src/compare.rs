@@ -0,0 +1,207 @@
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+use crate::{isolate, solve_part, Error, Part, PartResult, RunOptions, Solution, Variant};
+
+/// The name `aoc compare` gives [`Solution::part1`] and [`Solution::part2`].
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// How one implementation of a part did.
+#[derive(Debug, Clone)]
+pub struct VariantResult {
+    pub name: &'static str,
+    pub result: PartResult,
+}
+
+/// Every implementation of one part, run on the same parsed input.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: Part,
+    /// The default implementation first, then the variants in the order they were registered.
+    pub variants: Vec<VariantResult>,
+}
+
+impl Comparison {
+    /// True if every variant succeeded with the same answer.
+    pub fn agrees(&self) -> bool {
+        let mut answers = self
+            .variants
+            .iter()
+            .map(|variant| variant.result.outcome.as_ref().ok());
+        let first = answers.next().flatten();
+        first.is_some() && answers.all(|answer| answer == first)
+    }
+
+    fn fastest(&self) -> Option<Duration> {
+        self.variants
+            .iter()
+            .filter_map(|variant| variant.result.timing)
+            .map(|timing| timing.typical())
+            .min()
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = match (self.agrees(), self.variants.len()) {
+            (true, 1) => "no other variants",
+            (true, _) => "variants agree",
+            (false, _) => "variants disagree",
+        };
+        writeln!(f, "Day {} part {}: {verdict}", self.day, self.part)?;
+        let answers: Vec<_> = self
+            .variants
+            .iter()
+            .map(|variant| match &variant.result.outcome {
+                Ok(answer) => answer.clone(),
+                Err(err) => format!("error: {err}"),
+            })
+            .collect();
+        let name_width = self
+            .variants
+            .iter()
+            .map(|variant| variant.name.len())
+            .max()
+            .unwrap_or(0);
+        let answer_width = answers
+            .iter()
+            .map(|answer| answer.chars().count())
+            .max()
+            .unwrap_or(0);
+        let fastest = self.fastest();
+        for (variant, answer) in self.variants.iter().zip(&answers) {
+            write!(f, "  {:name_width$}  {answer:answer_width$}", variant.name)?;
+            if let Some(timing) = variant.result.timing {
+                let time = timing.typical();
+                write!(f, "  {time:>10.2?}")?;
+                match fastest {
+                    Some(fastest) if fastest == time => write!(f, "  fastest")?,
+                    Some(fastest) => write!(
+                        f,
+                        "  {:.1}x slower",
+                        time.as_secs_f64() / fastest.as_secs_f64()
+                    )?,
+                    None => {}
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn compare_part<S: Solution, A: Display>(
+    options: &RunOptions,
+    part: Part,
+    parsed: &S::Parsed<'_>,
+    variants: Vec<Variant<S, A>>,
+    locate: impl Fn(Error) -> Error,
+) -> Comparison {
+    let variants = variants
+        .into_iter()
+        .map(|(name, solve)| VariantResult {
+            name,
+            result: solve_part(options, part, || solve(parsed).map_err(&locate)),
+        })
+        .collect();
+    Comparison {
+        day: S::DAY,
+        part,
+        variants,
+    }
+}
+
+/// Parses `input` once, then runs and times every implementation of the requested part, or of both
+/// parts when `part` is `None`.
+pub fn compare<S: Solution>(input: &str, options: &RunOptions) -> Result<Vec<Comparison>, Error> {
+    let locate = |err: Error| err.locate(S::DAY, input);
+    let parsed = isolate::catch(|| Ok(S::parse(input)?)).map_err(locate)?;
+    let variants = S::variants();
+    let mut comparisons = Vec::new();
+    if options.part != Some(Part::Two) {
+        let mut part1 = vec![(DEFAULT_VARIANT, S::part1 as _)];
+        part1.extend(variants.part1);
+        comparisons.push(compare_part::<S, _>(
+            options,
+            Part::One,
+            &parsed,
+            part1,
+            locate,
+        ));
+    }
+    if options.part != Some(Part::One) {
+        let mut part2 = vec![(DEFAULT_VARIANT, S::part2 as _)];
+        part2.extend(variants.part2);
+        comparisons.push(compare_part::<S, _>(
+            options,
+            Part::Two,
+            &parsed,
+            part2,
+            locate,
+        ));
+    }
+    Ok(comparisons)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day6::Day6;
+    use crate::Timing;
+
+    fn variant(name: &'static str, outcome: Result<&str, Error>, micros: u64) -> VariantResult {
+        VariantResult {
+            name,
+            result: PartResult {
+                part: Part::One,
+                outcome: outcome.map(str::to_string),
+                timing: Some(Timing::Once(Duration::from_micros(micros))),
+                allocs: None,
+                verdict: None,
+            },
+        }
+    }
+
+    #[test]
+    fn agrees_test() {
+        let mut comparison = Comparison {
+            day: 6,
+            part: Part::One,
+            variants: vec![
+                variant("default", Ok("288"), 30),
+                variant("fast", Ok("288"), 10),
+            ],
+        };
+        assert!(comparison.agrees());
+        assert_eq!(
+            comparison.to_string(),
+            "Day 6 part 1: variants agree\n  \
+             default  288     30.00µs  3.0x slower\n  \
+             fast     288     10.00µs  fastest\n"
+        );
+        comparison.variants[1] = variant("fast", Ok("289"), 10);
+        assert!(!comparison.agrees());
+        comparison.variants[1] = variant("fast", Err(Error::Solve("no".to_string())), 10);
+        assert!(!comparison.agrees());
+    }
+
+    #[test]
+    fn compare_test() {
+        let input = include_str!("../example/day6.txt");
+        let comparisons = compare::<Day6>(input, &RunOptions::default()).unwrap();
+        assert_eq!(comparisons.len(), 2);
+        for comparison in &comparisons {
+            assert!(comparison.agrees(), "{comparison}");
+            assert!(comparison.variants.len() > 1);
+            assert_eq!(comparison.variants[0].name, DEFAULT_VARIANT);
+        }
+        let options = RunOptions {
+            part: Some(Part::Two),
+            ..RunOptions::default()
+        };
+        let comparisons = compare::<Day6>(input, &options).unwrap();
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].part, Part::Two);
+    }
+}
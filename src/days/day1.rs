@@ -0,0 +1,108 @@
+use crate::gen::Rng;
+use crate::scan::Scanner;
+use crate::{Error, ParseError, Solution};
+
+const LETTERS: &[char] = &['a', 'b', 'c', 'e', 'f', 'g', 'h', 'i', 'n', 'o', 'r', 't', 'v', 'x'];
+
+const NUMBER_STRINGS: [(&str, u32); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+/// The digit at the start of `bytes`, written as a digit or, with `match_words`, spelled out.
+fn digit_at(bytes: &[u8], match_words: bool) -> Option<u32> {
+    match bytes.first()? {
+        digit @ b'0'..=b'9' => Some((digit - b'0') as u32),
+        _ if match_words => NUMBER_STRINGS
+            .iter()
+            .find(|(word, _)| bytes.starts_with(word.as_bytes()))
+            .map(|(_, value)| *value),
+        _ => None,
+    }
+}
+
+/// Searches each line for its first digit from the front and its last from the back, so the
+/// middle of the line is never read.
+fn solver(input: &str, match_words: bool) -> Result<u32, ParseError> {
+    Scanner::new(input)
+        .lines()
+        .map(|line| {
+            let ln = line.rest();
+            let bytes = ln.as_bytes();
+            let digit = |i: usize| digit_at(&bytes[i..], match_words);
+            let first = (0..bytes.len()).find_map(digit);
+            let last = (0..bytes.len()).rev().find_map(digit);
+            match (first, last) {
+                (Some(f), Some(l)) => Ok((f * 10) + l),
+                _ => Err(ParseError::new(ln, "a line containing a digit")),
+            }
+        })
+        .sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Parsed<'a> = &'a str;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+    fn part1(input: &&str) -> Result<u32, Error> {
+        Ok(solver(input, false)?)
+    }
+    fn part2(input: &&str) -> Result<u32, Error> {
+        Ok(solver(input, true)?)
+    }
+
+    /// `size` lines of letters, digits and spelled out digits, each with at least one digit.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size {
+            let mut tokens: Vec<String> = (0..rng.range(1..=6))
+                .map(|_| match rng.range(0..=2) {
+                    0 => rng.range(1..=9).to_string(),
+                    1 => rng.choose(&NUMBER_STRINGS[1..]).0.to_string(),
+                    _ => (0..rng.range(1..=4))
+                        .map(|_| *rng.choose(LETTERS))
+                        .collect(),
+                })
+                .collect();
+            // Part 1 only counts digits, so every line needs one
+            if !tokens.concat().contains(|c: char| c.is_ascii_digit()) {
+                tokens.push(rng.range(1..=9).to_string());
+                rng.shuffle(&mut tokens);
+            }
+            input.push_str(&tokens.concat());
+            input.push('\n');
+        }
+        Some(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_digit_test() {
+        let result = crate::solve_puzzles::<Day1>("1abc2\nnodigits\n", &Default::default());
+        let Err(err) = &result.parts[0].outcome else {
+            panic!("expected an error");
+        };
+        assert_eq!(
+            err.to_string(),
+            "day 1, line 2, column 1: expected a line containing a digit, found 'nodigits'"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt::Write;
+
+use crate::gen::Rng;
+use crate::scan::Scanner;
+use crate::{Error, ParseError, Solution};
+
+#[derive(Debug, Default)]
+struct CubeSet {
+    red: i32,
+    green: i32,
+    blue: i32,
+}
+
+#[derive(Debug, Default)]
+pub struct Game {
+    id: i32,
+    max_red: i32,
+    max_green: i32,
+    max_blue: i32,
+}
+
+impl Game {
+    fn from_line(mut line: Scanner) -> Result<Self, ParseError> {
+        line.expect("Game")?;
+        let mut game = Game {
+            id: line.unsigned()?,
+            ..Game::default()
+        };
+        line.expect(":")?;
+        // Only the most cubes of each colour matter, so the draws themselves aren't kept
+        loop {
+            let count: i32 = line.unsigned()?;
+            let max = match line.word()? {
+                "red" => &mut game.max_red,
+                "green" => &mut game.max_green,
+                "blue" => &mut game.max_blue,
+                colour => return Err(ParseError::new(colour, "red, green or blue")),
+            };
+            *max = count.max(*max);
+            if !line.eat(b',') && !line.eat(b';') {
+                line.end()?;
+                return Ok(game);
+            }
+        }
+    }
+    fn is_valid(&self, test: &CubeSet) -> bool {
+        test.red >= self.max_red && test.green >= self.max_green && test.blue >= self.max_blue
+    }
+    fn min_cubes_power(&self) -> i32 {
+        self.max_red * self.max_green * self.max_blue
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Parsed<'a> = Vec<Game>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+        Scanner::new(input).lines().map(Game::from_line).collect()
+    }
+
+    fn part1(games: &Vec<Game>) -> Result<i32, Error> {
+        let test_set = CubeSet {
+            red: 12,
+            green: 13,
+            blue: 14,
+        };
+
+        Ok(games
+            .iter()
+            .filter(|game| game.is_valid(&test_set))
+            .fold(0, |acc, game| acc + game.id))
+    }
+
+    fn part2(games: &Vec<Game>) -> Result<i32, Error> {
+        Ok(games.iter().map(|game| game.min_cubes_power()).sum())
+    }
+
+    /// `size` games of one to six draws, each of 1 to 15 cubes of some of the colours, so some
+    /// games are possible.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for id in 1..=size {
+            let draws: Vec<String> = (0..rng.range(1..=6))
+                .map(|_| {
+                    let mut colours = ["red", "green", "blue"];
+                    rng.shuffle(&mut colours);
+                    colours[..rng.range(1..=3)]
+                        .iter()
+                        .map(|colour| format!("{} {colour}", rng.range(1..=15)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            writeln!(input, "Game {id}: {}", draws.join("; ")).unwrap();
+        }
+        Some(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_colour_test() {
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 blue; 8 purple\n";
+        let result = crate::solve_puzzles::<Day2>(input, &Default::default());
+        let Err(err) = &result.parse else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(
+            err.to_string(),
+            "day 2, line 2, column 19: expected red, green or blue, found 'purple'"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+
+use crate::error::parse_number;
+use crate::gen::Rng;
+use crate::grid::{Grid, Point};
+use crate::{Error, ParseError, Solution};
+
+const SYMBOLS: &[char] = &['*', '#', '+', '$', '/', '=', '%', '@', '&', '-'];
+
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
+struct PartNumber {
+    value: i32,
+    start: Point,
+    end: Point,
+}
+
+impl PartNumber {
+    /// The points covered by the number's digits.
+    fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (self.start.x..=self.end.x).map(|x| Point::new(x, self.start.y))
+    }
+    fn is_valid(&self, grid: &Grid<Cell>) -> bool {
+        self.points().any(|point| {
+            grid.neighbours8(point)
+                .any(|(_, cell)| matches!(cell, Cell::Symbol(_)))
+        })
+    }
+    fn includes_point(&self, point: &Point) -> bool {
+        point.y == self.start.y && (self.start.x..=self.end.x).contains(&point.x)
+    }
+}
+
+#[derive(Debug)]
+enum Cell {
+    Empty,
+    Digit,
+    Symbol(char),
+}
+
+impl From<char> for Cell {
+    fn from(c: char) -> Self {
+        match c {
+            c if c.is_ascii_digit() => Cell::Digit,
+            '.' => Cell::Empty,
+            c => Cell::Symbol(c),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Schematic {
+    grid: Grid<Cell>,
+    part_numbers: Vec<PartNumber>,
+}
+
+impl Schematic {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, Cell::from)?;
+        let mut part_numbers = Vec::new();
+        for (y_pos, ln) in input.lines().enumerate() {
+            let y_pos = y_pos as i64;
+            // A '.' past the end of the line finishes a number at the end of it
+            let end = (ln.chars().count(), (ln.len(), '.'));
+            let mut number_start: Option<(usize, usize)> = None;
+            for (x_pos, (i, c)) in ln.char_indices().enumerate().chain([end]) {
+                match (c.is_ascii_digit(), number_start) {
+                    (true, None) => number_start = Some((x_pos, i)),
+                    (false, Some((start_x, start))) => {
+                        part_numbers.push(PartNumber {
+                            value: parse_number(&ln[start..i])?,
+                            start: Point::new(start_x as i64, y_pos),
+                            end: Point::new(x_pos as i64 - 1, y_pos),
+                        });
+                        number_start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(Schematic { grid, part_numbers })
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Parsed<'a> = Schematic;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Schematic, ParseError> {
+        Schematic::from_input(input)
+    }
+
+    fn part1(schematic: &Schematic) -> Result<i32, Error> {
+        Ok(schematic
+            .part_numbers
+            .iter()
+            .filter(|pn| pn.is_valid(&schematic.grid))
+            .fold(0, |acc, pn| acc + pn.value))
+    }
+
+    fn part2(schematic: &Schematic) -> Result<i32, Error> {
+        let mut result = 0;
+        for (point, cell) in schematic.grid.iter() {
+            if let Cell::Symbol('*') = cell {
+                let neighbors: Vec<Point> = schematic
+                    .grid
+                    .neighbours8(point)
+                    .filter(|(_, cell)| matches!(cell, Cell::Digit))
+                    .map(|(neighbor, _)| neighbor)
+                    .collect();
+
+                let adj_part_numbers: HashSet<&PartNumber> = schematic
+                    .part_numbers
+                    .iter()
+                    .filter(|pn| neighbors.iter().any(|point| pn.includes_point(point)))
+                    .collect();
+                if adj_part_numbers.len() == 2 {
+                    result += adj_part_numbers.iter().map(|pn| pn.value).product::<i32>();
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// A `size` by `size` schematic of numbers of up to three digits and symbols, mostly '.'.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size {
+            let mut row = String::new();
+            while row.len() < size {
+                match rng.range(0..=9) {
+                    0..=2 => {
+                        let digits = rng.range(1..=3).min(size - row.len());
+                        row.push_str(&rng.range(1..=9).to_string());
+                        for _ in 1..digits {
+                            row.push_str(&rng.range(0..=9).to_string());
+                        }
+                        // Two numbers next to each other would read as one
+                        if row.len() < size {
+                            match rng.chance(0.3) {
+                                true => row.push(*rng.choose(SYMBOLS)),
+                                false => row.push('.'),
+                            }
+                        }
+                    }
+                    3 => row.push(*rng.choose(SYMBOLS)),
+                    _ => row.push('.'),
+                }
+            }
+            input.push_str(&row);
+            input.push('\n');
+        }
+        Some(input)
+    }
+}
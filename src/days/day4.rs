@@ -0,0 +1,132 @@
+use std::fmt::Write;
+
+use crate::gen::Rng;
+use crate::scan::Scanner;
+use crate::{Error, ParseError, Solution};
+
+/// The most copies of any one card a generated input gives in part 2.
+const MAX_COPIES: u32 = 1_000_000;
+
+#[derive(Debug)]
+pub struct Card {
+    wins: usize,
+}
+
+impl Card {
+    /// Counts the matches while reading the card, keeping the winning numbers in a bitset rather
+    /// than a list. The puzzle's numbers all have two digits, so a byte is plenty.
+    fn from_line(mut line: Scanner) -> Result<Self, ParseError> {
+        line.expect("Card")?;
+        line.unsigned::<u32>()?;
+        line.expect(":")?;
+        let mut winning = [0u128; 2];
+        while !line.eat(b'|') {
+            let number: u8 = line.unsigned()?;
+            winning[number as usize / 128] |= 1 << (number % 128);
+        }
+        let mut wins = 0;
+        while !line.is_empty() {
+            let number: u8 = line.unsigned()?;
+            wins += (winning[number as usize / 128] >> (number % 128) & 1) as usize;
+        }
+        Ok(Card { wins })
+    }
+    fn wins(&self) -> usize {
+        self.wins
+    }
+    fn points(&self) -> u32 {
+        match self.wins() {
+            0 => 0,
+            pow => 2u32.pow(pow as u32 - 1),
+        }
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Parsed<'a> = Vec<Card>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Card>, ParseError> {
+        Scanner::new(input).lines().map(Card::from_line).collect()
+    }
+
+    fn part1(cards: &Vec<Card>) -> Result<u32, Error> {
+        Ok(cards.iter().map(|card| card.points()).sum())
+    }
+
+    fn part2(cards: &Vec<Card>) -> Result<u32, Error> {
+        let mut copies: Vec<u32> = vec![1; cards.len()];
+        for i in 0..cards.len() {
+            let matches = cards[i].wins();
+            let current_copies = copies[i];
+            let id = i + 1;
+            for below_card_id in id + 1..id + 1 + matches {
+                let copies = copies.get_mut(below_card_id - 1).ok_or_else(|| {
+                    Error::Solve(format!("card {id} wins copies past the last card"))
+                })?;
+                *copies += current_copies;
+            }
+        }
+        Ok(copies.iter().sum())
+    }
+
+    /// `size` cards of 10 winning numbers and 25 numbers you have. No card wins copies of cards
+    /// past the end, and matches are limited so no card has more than a million copies.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut copies = vec![1; size];
+        let mut input = String::new();
+        for card in 0..size {
+            let mut matches = rng.range(0..=5).min(size - card - 1);
+            while copies[card + 1..=card + matches]
+                .iter()
+                .any(|copies_won| copies_won + copies[card] > MAX_COPIES)
+            {
+                matches -= 1;
+            }
+            for won in card + 1..=card + matches {
+                copies[won] += copies[card];
+            }
+            let mut numbers: Vec<usize> = (1..=99).collect();
+            rng.shuffle(&mut numbers);
+            let (winning, rest) = numbers.split_at(10);
+            let mut have: Vec<usize> = winning[..matches].to_vec();
+            have.extend(&rest[..25 - matches]);
+            rng.shuffle(&mut have);
+            let join = |numbers: &[usize]| {
+                numbers
+                    .iter()
+                    .map(|number| format!("{number:>2}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            writeln!(
+                input,
+                "Card {:>3}: {} | {}",
+                card + 1,
+                join(winning),
+                join(&have)
+            )
+            .unwrap();
+        }
+        Some(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_past_last_card_test() {
+        assert_eq!(
+            Day4::solve_part2("Card 1: 1 2 | 1 2\n"),
+            Err(Error::Solve(
+                "card 1 wins copies past the last card".to_string()
+            ))
+        );
+    }
+}
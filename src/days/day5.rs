@@ -0,0 +1,273 @@
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::cancel;
+use crate::gen::Rng;
+use crate::interval::{IntervalMap, IntervalSet};
+use crate::error::parse_number;
+use crate::parse;
+use crate::{Error, ParseError, Solution, Variants};
+
+/// The puzzle's categories, used to name the maps of generated almanacs.
+const CATEGORIES: [&str; 7] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+];
+/// Generated almanacs map numbers below this.
+const GENERATED_SPACE: usize = 1_000_000;
+
+#[derive(Debug)]
+pub struct Almanac<'a> {
+    seeds: Vec<usize>,
+    maps: HashMap<&'a str, GardenMap<'a>>,
+}
+
+#[derive(Debug)]
+struct GardenMap<'a> {
+    destination: &'a str,
+    ranges: IntervalMap<usize>,
+}
+
+fn parse_input(input: &str) -> Result<Almanac<'_>, ParseError> {
+    let mut sections = parse::sections(input);
+    let seed_str = sections
+        .next()
+        .ok_or_else(|| ParseError::new(input, "'seeds: '"))?;
+    let seeds_value = parse::value(seed_str, "seeds")?;
+    let seeds: Vec<usize> = parse::numbers(seeds_value).collect::<Result<_, _>>()?;
+    // Part 2 reads the seeds as pairs of start and length
+    if seeds.len() % 2 == 1 {
+        let last = seeds_value.split_whitespace().last().unwrap_or(seeds_value);
+        return Err(ParseError::new(last, "an even number of seeds").found(format!("'{last}' on its own")));
+    }
+
+    let maps: HashMap<_, _> = sections
+        .map(|map_str| {
+            let mut lines = map_str.lines();
+            let header = lines
+                .next()
+                .ok_or_else(|| ParseError::new(map_str, "a map header"))?;
+            let (name, _) = parse::key_value(header)?;
+            let (source, destination) = name
+                .strip_suffix(" map")
+                .ok_or_else(|| ParseError::after(name, "' map'"))?
+                .split_once("-to-")
+                .ok_or_else(|| ParseError::new(name, "'<source>-to-<destination> map'"))?;
+
+            let mut ranges = IntervalMap::new();
+            for ln in lines {
+                let mut fields = ln.split_whitespace();
+                let mut next_number = || -> Result<(&str, usize), ParseError> {
+                    let field = fields
+                        .next()
+                        .ok_or_else(|| ParseError::after(ln, "a number"))?;
+                    Ok((field, parse_number(field)?))
+                };
+                let (_, destination) = next_number()?;
+                let (_, start) = next_number()?;
+                let (length_str, length) = next_number()?;
+                // Both ends have to fit, or mapping values through the range would overflow
+                let end = start
+                    .checked_add(length)
+                    .filter(|_| destination.checked_add(length).is_some())
+                    .ok_or_else(|| ParseError::new(length_str, "a range that fits in usize"))?;
+                ranges.insert(start..end, destination);
+            }
+            Ok((
+                source,
+                GardenMap {
+                    destination,
+                    ranges,
+                },
+            ))
+        })
+        .collect::<Result<_, ParseError>>()?;
+    if maps.is_empty() {
+        return Err(ParseError::after(seed_str, "a blank line before the maps"));
+    }
+
+    Ok(Almanac { seeds, maps })
+}
+
+/// A random almanac of `maps` maps chained from seed to location, each with `ranges`
+/// non-overlapping ranges, and four ranges of up to 10,000 seeds.
+pub fn generate_almanac(rng: &mut Rng, maps: usize, ranges: usize) -> String {
+    let maps = maps.max(1);
+    let ranges = ranges.clamp(1, GENERATED_SPACE / 2);
+    let mut categories: Vec<String> = (0..maps)
+        .map(|i| match CATEGORIES.get(i) {
+            Some(category) => category.to_string(),
+            None => format!("category{i}"),
+        })
+        .collect();
+    categories.push("location".to_string());
+
+    let seeds: Vec<String> = (0..4)
+        .map(|_| {
+            let start = rng.range(0..=GENERATED_SPACE - 10_000);
+            format!("{start} {}", rng.range(1..=10_000))
+        })
+        .collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+    // One range per slot of the space keeps the source ranges from overlapping
+    let slot = GENERATED_SPACE / ranges;
+    for pair in categories.windows(2) {
+        let mut lines: Vec<String> = (0..ranges)
+            .map(|i| {
+                let source = i * slot + rng.range(0..=slot / 2 - 1);
+                let length = rng.range(1..=slot / 2);
+                let destination = rng.range(0..=GENERATED_SPACE);
+                format!("{destination} {source} {length}")
+            })
+            .collect();
+        rng.shuffle(&mut lines);
+        write!(input, "\n{}-to-{} map:\n{}\n", pair[0], pair[1], lines.join("\n")).unwrap();
+    }
+    input
+}
+
+/// Follows the maps from seed to location, applying `step` with each one in turn.
+fn search_for_location<T>(
+    almanac: &Almanac,
+    start: T,
+    step: impl Fn(&GardenMap, T) -> T,
+) -> Result<T, Error> {
+    let mut category = "seed";
+    let mut value = start;
+    // Any chain longer than the number of maps must go round in a loop
+    for _ in 0..almanac.maps.len() {
+        let map = almanac
+            .maps
+            .get(category)
+            .ok_or_else(|| Error::Solve(format!("no map from {category}")))?;
+        value = step(map, value);
+        category = map.destination;
+        if category == "location" {
+            return Ok(value);
+        }
+    }
+    Err(Error::Solve("the maps never reach location".to_string()))
+}
+
+/// The seed ranges from the pairs of numbers in the seeds line.
+fn seed_ranges(almanac: &Almanac) -> Result<IntervalSet<usize>, Error> {
+    almanac
+        .seeds
+        .chunks(2)
+        .map(|seed_pair| match seed_pair[0].checked_add(seed_pair[1]) {
+            Some(end) => Ok(seed_pair[0]..end),
+            None => Err(Error::Solve(format!(
+                "the seed range {} {} goes past usize::MAX",
+                seed_pair[0], seed_pair[1]
+            ))),
+        })
+        .collect()
+}
+
+/// Tries every seed in every range.
+fn brute_force(almanac: &Almanac) -> Result<usize, Error> {
+    let token = cancel::token();
+    seed_ranges(almanac)?
+        .ranges()
+        .iter()
+        .filter_map(|range| {
+            range
+                .clone()
+                .into_par_iter()
+                .map(|number| {
+                    token.check()?;
+                    search_for_location(almanac, number, |map, n| map.ranges.get(n))
+                })
+                .try_reduce_with(|a, b| Ok(a.min(b)))
+        })
+        .collect::<Result<Vec<_>, Error>>()?
+        .into_iter()
+        .min()
+        .ok_or_else(|| Error::Solve("no seeds".to_string()))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Parsed<'a> = Almanac<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Almanac<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(almanac: &Almanac) -> Result<usize, Error> {
+        almanac
+            .seeds
+            .iter()
+            .map(|number| search_for_location(almanac, *number, |map, n| map.ranges.get(n)))
+            .collect::<Result<Vec<_>, Error>>()?
+            .into_iter()
+            .min()
+            .ok_or_else(|| Error::Solve("no seeds".to_string()))
+    }
+
+    /// Maps whole ranges of seeds at once, splitting them where the map's ranges start and end.
+    fn part2(almanac: &Almanac) -> Result<usize, Error> {
+        search_for_location(almanac, seed_ranges(almanac)?, |map, seeds| {
+            map.ranges.map_set(&seeds)
+        })?
+        .min()
+        .ok_or_else(|| Error::Solve("no seeds".to_string()))
+    }
+
+    fn variants() -> Variants<Self> {
+        Variants {
+            part1: Vec::new(),
+            part2: vec![("brute_force", brute_force)],
+        }
+    }
+
+    /// An almanac with the puzzle's seven maps, each of `size` ranges.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_almanac(rng, CATEGORIES.len(), size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPS: &str = "seed-to-location map:\n50 98 2\n";
+
+    #[test]
+    fn odd_seed_count_test() {
+        let input = format!("seeds: 79 14 55\n\n{MAPS}");
+        let err = Day5::parse(&input).unwrap_err().locate(&input);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 14: expected an even number of seeds, found '55' on its own"
+        );
+    }
+
+    #[test]
+    fn map_range_overflow_test() {
+        let input = format!("seeds: 1 2\n\n{MAPS}1 {} 5\n", usize::MAX);
+        let err = Day5::parse(&input).unwrap_err().locate(&input);
+        assert_eq!(
+            err.to_string(),
+            "line 5, column 24: expected a range that fits in usize, found '5'"
+        );
+    }
+
+    #[test]
+    fn seed_range_overflow_test() {
+        let input = format!("seeds: {} 2\n\n{MAPS}", usize::MAX);
+        let err = Day5::solve_part2(&input).unwrap_err();
+        assert!(matches!(err, Error::Solve(message) if message.contains("past usize::MAX")));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::gen::Rng;
+use crate::scan::Scanner;
+use crate::{Error, ParseError, Solution, Variants};
+
+pub type Races = Vec<Race>;
+
+#[derive(Debug)]
+pub struct Race {
+    record: usize,
+    time: usize,
+}
+
+impl Race {
+    fn get_distances(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..=self.time).map(|n| n * (self.time - n))
+    }
+    fn get_distance_count(&self) -> usize {
+        self.get_distances().filter(|d| *d > self.record).count()
+    }
+    fn get_distance_count_with_boundries(&self) -> usize {
+        let mut start_winning: Option<usize> = None;
+        let mut finish_winning: Option<usize> = None;
+        for n in 0..=self.time {
+            let distance = n * (self.time - n);
+            if distance > self.record {
+                start_winning = Some(n);
+                break;
+            }
+        }
+        for n in (0..=self.time).rev() {
+            let distance = n * (self.time - n);
+            if distance > self.record {
+                finish_winning = Some(n);
+                break;
+            }
+        }
+        match (start_winning, finish_winning) {
+            (Some(start), Some(finish)) => finish - start + 1,
+            // No way to beat the record
+            _ => 0,
+        }
+    }
+}
+
+/// Reads the numbers after `key`, and the one number they make with the spaces between them
+/// removed.
+fn parse_line(mut line: Scanner, key: &str) -> Result<(Vec<usize>, usize), ParseError> {
+    line.expect(key)?;
+    line.expect(":")?;
+    let numbers_str = line.rest().trim();
+    let mut numbers = Vec::new();
+    while !line.is_empty() {
+        numbers.push(line.unsigned()?);
+    }
+    if numbers.is_empty() {
+        return Err(line.error("a number"));
+    }
+    // Only digits and spaces are left, so the kerned number is the digits in order
+    let kerned = numbers_str
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .try_fold(0usize, |acc, digit| {
+            acc.checked_mul(10)?.checked_add((digit - b'0') as usize)
+        })
+        .ok_or_else(|| ParseError::new(numbers_str, "a number that fits in usize"))?;
+    Ok((numbers, kerned))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Parsed<'a> = (Races, Race);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<(Races, Race), ParseError> {
+        let mut lines = Scanner::new(input).lines();
+        let mut next_line = |key| match lines.next() {
+            Some(line) => Ok((line.rest(), parse_line(line, key)?)),
+            None => Err(ParseError::after(input, "another line")),
+        };
+        let (times_str, (times, time)) = next_line("Time")?;
+        let (records_str, (records, record)) = next_line("Distance")?;
+        // Every race needs both a time and a record
+        if times.len() != records.len() {
+            let (shorter, expected) = match times.len() < records.len() {
+                true => (times_str, records.len()),
+                false => (records_str, times.len()),
+            };
+            return Err(ParseError::after(shorter, format!("{expected} numbers")));
+        }
+        let races = times
+            .into_iter()
+            .zip(records)
+            .map(|(time, record)| Race { record, time })
+            .collect();
+        Ok((races, Race { record, time }))
+    }
+
+    fn part1((races, _): &(Races, Race)) -> Result<usize, Error> {
+        Ok(races.iter().map(Race::get_distance_count).product())
+    }
+
+    fn part2((_, race): &(Races, Race)) -> Result<usize, Error> {
+        Ok(race.get_distance_count_with_boundries())
+    }
+
+    /// `size` races, at most four so the joined up race in part 2 fits in a `usize`. Times are two
+    /// digits and records at most three, so every race, including the joined one, can be won.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let races: Vec<Race> = (0..size.clamp(1, 4))
+            .map(|_| {
+                let time = rng.range(40..=99);
+                let best = (time / 2) * (time - time / 2);
+                Race {
+                    time,
+                    record: rng.range(1..=(best - 1).min(999)),
+                }
+            })
+            .collect();
+        let row = |value: fn(&Race) -> usize| {
+            races
+                .iter()
+                .map(|race| format!("{:>4}", value(race)))
+                .collect::<String>()
+        };
+        Some(format!(
+            "Time:    {}\nDistance:{}\n",
+            row(|race| race.time),
+            row(|race| race.record)
+        ))
+    }
+
+    fn variants() -> Variants<Self> {
+        Variants {
+            part1: vec![("boundaries", |(races, _)| {
+                Ok(races
+                    .iter()
+                    .map(Race::get_distance_count_with_boundries)
+                    .product())
+            })],
+            part2: vec![("brute_force", |(_, race)| Ok(race.get_distance_count()))],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unwinnable_race_test() {
+        let input = "Time: 1 2\nDistance: 5 2\n";
+        assert_eq!(Day6::solve_part1(input), Ok(0));
+        assert_eq!(Day6::solve_part2(input), Ok(0));
+    }
+
+    #[test]
+    fn uneven_lines_test() {
+        let input = "Time: 7 15 30\nDistance: 9 40\n";
+        let err = Day6::parse(input).unwrap_err().locate(input);
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 15: expected 3 numbers, found end of line"
+        );
+        let input = "Time: 7\nDistance: 9 40\n";
+        let err = Day6::parse(input).unwrap_err().locate(input);
+        assert_eq!(err.position.map(|position| position.line), Some(1));
+    }
+}
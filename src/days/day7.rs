@@ -0,0 +1,219 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use crate::error::parse_number;
+use crate::gen::Rng;
+use crate::{Error, ParseError, Solution};
+
+const CARDS: &str = "23456789TJQKA";
+/// The most hands a generated input has. With bids up to 1000 the winnings of 2900 hands are at
+/// most 1000 * 2900 * 2901 / 2, which still fits in a `u32`.
+const MAX_HANDS: usize = 2900;
+
+#[derive(Debug)]
+pub struct Hand<'a> {
+    cards: &'a str,
+    bid: u32,
+}
+
+impl<'a> Hand<'a> {
+    fn from_line(input: &'a str) -> Result<Self, ParseError> {
+        let (cards, bid_str) = input
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(input, "'<cards> <bid>'"))?;
+        if let Some((i, c)) = cards.char_indices().find(|(_, c)| !CARDS.contains(*c)) {
+            return Err(ParseError::new(&cards[i..i + c.len_utf8()], "a card"));
+        }
+        if cards.len() != 5 {
+            return Err(ParseError::new(cards, "5 cards"));
+        }
+        Ok(Hand {
+            cards,
+            bid: parse_number(bid_str)?,
+        })
+    }
+}
+
+#[derive(Debug, Eq)]
+struct Game {
+    hand: Vec<u32>,
+    bid: u32,
+    strength: u32,
+}
+
+impl Game {
+    fn from_hand(input: &Hand, wildcards: bool) -> Self {
+        let hand: Vec<_> = input
+            .cards
+            .chars()
+            .map(|c| match c {
+                c if c.is_ascii_digit() => c.to_digit(10).unwrap(),
+                'T' => 10,
+                'J' => match wildcards {
+                    true => 1,
+                    false => 11,
+                },
+                'Q' => 12,
+                'K' => 13,
+                'A' => 14,
+                _ => unreachable!("cards are checked when parsed"),
+            })
+            .collect();
+        let mut counts = hand.iter().counts();
+        if wildcards {
+            let jokers = *counts.get(&1).unwrap_or(&0);
+            if jokers > 0 && jokers < 5 {
+                let mut h_val = 0usize;
+                let mut h_key = 0u32;
+                for (k, v) in counts.iter() {
+                    if **k != 1 && v > &h_val {
+                        h_val = *v;
+                        h_key = **k;
+                    }
+                }
+                let highest_value = counts.get_mut(&h_key).unwrap();
+                if h_key != 1 {
+                    *highest_value += jokers;
+                    counts.remove(&1u32);
+                }
+            }
+        }
+        let strength = match counts.len() {
+            1 => 7,
+            2 => {
+                if counts.iter().any(|(_, v)| *v == 4) {
+                    6
+                } else {
+                    5
+                }
+            }
+            3 => {
+                if counts.iter().any(|(_, v)| *v == 3) {
+                    4
+                } else {
+                    3
+                }
+            }
+            4 => 2,
+            5 => 1,
+            _ => panic!("Too many cards!"),
+        };
+
+        Self {
+            hand,
+            bid: input.bid,
+            strength,
+        }
+    }
+}
+
+impl PartialEq for Game {
+    fn eq(&self, other: &Self) -> bool {
+        self.hand == other.hand
+    }
+}
+
+impl PartialOrd for Game {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Game {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.strength.cmp(&other.strength) {
+            Ordering::Equal => {
+                for i in 0..5 {
+                    if self.hand[i] != other.hand[i] {
+                        return self.hand[i].cmp(&other.hand[i]);
+                    }
+                }
+                Ordering::Equal
+            }
+            x => x,
+        }
+    }
+}
+
+fn total_winnings(hands: &[Hand], wildcards: bool) -> u32 {
+    let mut games: Vec<_> = hands
+        .iter()
+        .map(|hand| Game::from_hand(hand, wildcards))
+        .collect();
+    games.sort();
+    games
+        .iter()
+        .enumerate()
+        .fold(0u32, |acc, (i, game)| acc + (game.bid * (i as u32 + 1)))
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Parsed<'a> = Vec<Hand<'a>>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Hand<'_>>, ParseError> {
+        input.lines().map(Hand::from_line).collect()
+    }
+
+    fn part1(hands: &Vec<Hand>) -> Result<u32, Error> {
+        Ok(total_winnings(hands, false))
+    }
+
+    fn part2(hands: &Vec<Hand>) -> Result<u32, Error> {
+        Ok(total_winnings(hands, true))
+    }
+
+    /// `size` different hands with bids up to 1000. Hands are drawn from one to five different
+    /// labels, so every type of hand turns up. `size` is capped at [`MAX_HANDS`] so the winnings
+    /// fit in a `u32`.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.min(MAX_HANDS);
+        let labels: Vec<char> = CARDS.chars().collect();
+        let mut seen = HashSet::new();
+        let mut input = String::new();
+        while seen.len() < size {
+            let mut kinds = labels.clone();
+            rng.shuffle(&mut kinds);
+            kinds.truncate(rng.range(1..=5));
+            let mut cards = kinds.clone();
+            while cards.len() < 5 {
+                cards.push(*rng.choose(&kinds));
+            }
+            rng.shuffle(&mut cards);
+            let cards: String = cards.into_iter().collect();
+            if seen.insert(cards.clone()) {
+                writeln!(input, "{cards} {}", rng.range(1..=1000)).unwrap();
+            }
+        }
+        Some(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_card_test() {
+        let err = Day7::parse("32T3K 765\nT5X5J 684\n").unwrap_err();
+        assert_eq!(err.expected, "a card");
+        assert_eq!(err.found, "'X'");
+        assert_eq!(Day7::parse("32T3 765").unwrap_err().expected, "5 cards");
+    }
+
+    /// Past the cap, a generated input still has the most hands and the winnings don't overflow.
+    #[test]
+    fn generate_largest_test() {
+        let input = Day7::generate(&mut Rng::new(0), usize::MAX).unwrap();
+        assert_eq!(input.lines().count(), MAX_HANDS);
+        assert!(Day7::solve_part1(&input).is_ok());
+        assert!(Day7::solve_part2(&input).is_ok());
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+
+use crate::cancel;
+use crate::gen::Rng;
+use crate::math;
+use crate::parse;
+use crate::{Error, ParseError, Solution};
+
+/// Characters in the names of generated nodes.
+const NAME_CHARS: &[char] = &[
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
+    'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+];
+
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    Left,
+    Right,
+}
+
+type Branch<'a> = (&'a str, &'a str);
+
+#[derive(Debug)]
+pub struct Network<'a> {
+    directions: Vec<Direction>,
+    map: HashMap<&'a str, Branch<'a>>,
+}
+
+fn parse_input(input: &str) -> Result<Network<'_>, ParseError> {
+    let mut sections = parse::sections(input);
+    let direction_str = sections
+        .next()
+        .ok_or_else(|| ParseError::new(input, "'L' or 'R'"))?;
+    let branches_str = sections
+        .next()
+        .ok_or_else(|| ParseError::after(direction_str, "a blank line after the directions"))?;
+    let directions: Vec<Direction> = direction_str
+        .char_indices()
+        .map(|(i, c)| match c {
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(ParseError::new(&direction_str[i..i + c.len_utf8()], "'L' or 'R'")),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let branches: Vec<(&str, Branch)> = branches_str
+        .lines()
+        .map(|ln| {
+            let (source_str, dests_str) = ln
+                .split_once(" = (")
+                .ok_or_else(|| ParseError::new(ln, "'<node> = (<left>, <right>)'"))?;
+            let (left_target, right_target) = dests_str
+                .strip_suffix(')')
+                .ok_or_else(|| ParseError::after(ln, "')'"))?
+                .split_once(", ")
+                .ok_or_else(|| ParseError::new(dests_str, "'<left>, <right>)'"))?;
+            Ok((source_str, (left_target, right_target)))
+        })
+        .collect::<Result<_, ParseError>>()?;
+    let map: HashMap<&str, Branch> = branches.iter().copied().collect();
+    // Checked in input order, so the first bad name is the one reported
+    for (_, (left, right)) in &branches {
+        if let Some(missing) = [left, right].into_iter().find(|node| !map.contains_key(*node)) {
+            return Err(ParseError::new(missing, "a node defined in the network"));
+        }
+    }
+    Ok(Network { directions, map })
+}
+
+/// Hands out three-character names ending in one of `last`, each at most once, in a scrambled
+/// order. `AAA` and `ZZZ` are never handed out, so there are just under `36 * 36 * last.len()`.
+fn names<'a>(rng: &mut Rng, last: &'a [char]) -> impl FnMut() -> String + 'a {
+    // Coprime with 36 * 36 * 1 and 36 * 36 * 34, so stepping by it visits every name once
+    const STRIDE: usize = 7919;
+    let base = NAME_CHARS.len();
+    let space = base * base * last.len();
+    let mut n = rng.range(0..=space - 1);
+    move || loop {
+        n = (n + STRIDE) % space;
+        let (rest, end) = (n / last.len(), last[n % last.len()]);
+        let name: String = [NAME_CHARS[rest / base], NAME_CHARS[rest % base], end]
+            .iter()
+            .collect();
+        if name != "AAA" && name != "ZZZ" {
+            return name;
+        }
+    }
+}
+
+/// A random network of `directions` left/right steps and `ghosts` starting nodes, one of them
+/// `AAA`. Each ghost follows a cycle through exactly one `..Z` node, which it first reaches after
+/// a whole number of passes through the directions and then again at the same interval, so the
+/// ghosts all meet after the lcm of those lengths. `AAA`'s cycle goes through `ZZZ`.
+///
+/// `ghosts` is capped at 20, and `directions` so that every node still gets its own name.
+pub fn generate_network(rng: &mut Rng, ghosts: usize, directions: usize) -> String {
+    let ghosts = ghosts.clamp(1, 20);
+    let middle: Vec<char> = NAME_CHARS
+        .iter()
+        .copied()
+        .filter(|c| !matches!(c, 'A' | 'Z'))
+        .collect();
+    // Each ghost's path is up to 7 passes through the directions, nearly all of them middle names
+    let most_directions = NAME_CHARS.len() * NAME_CHARS.len() * middle.len() / (7 * ghosts);
+    let directions: Vec<char> = (0..directions.clamp(1, most_directions))
+        .map(|_| *rng.choose(&['L', 'R']))
+        .collect();
+    let mut start_name = names(rng, &['A']);
+    let mut middle_name = names(rng, &middle);
+    let mut end_name = names(rng, &['Z']);
+    let paths: Vec<Vec<String>> = (0..ghosts)
+        .map(|ghost| {
+            let length = rng.range(1..=7) * directions.len();
+            let mut path = vec![match ghost {
+                0 => "AAA".to_string(),
+                _ => start_name(),
+            }];
+            for _ in 1..length {
+                path.push(middle_name());
+            }
+            path.push(match ghost {
+                0 => "ZZZ".to_string(),
+                _ => end_name(),
+            });
+            path
+        })
+        .collect();
+
+    let nodes: Vec<&String> = paths.iter().flatten().collect();
+    let mut lines = Vec::new();
+    for path in &paths {
+        let end = path.len() - 1;
+        for (position, node) in path.iter().enumerate() {
+            // The end is reached on a multiple of the directions, so it carries on like the start
+            let next = &path[if position == end { 1 } else { position + 1 }];
+            let other = *rng.choose(&nodes);
+            let (left, right) = match directions[position % directions.len()] {
+                'L' => (next, other),
+                _ => (other, next),
+            };
+            lines.push(format!("{node} = ({left}, {right})"));
+        }
+    }
+    rng.shuffle(&mut lines);
+    let directions: String = directions.into_iter().collect();
+    format!("{directions}\n\n{}\n", lines.join("\n"))
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Parsed<'a> = Network<'a>;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Network<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(network: &Network) -> Result<u32, Error> {
+        let Network { directions, map } = network;
+
+        if !map.contains_key("AAA") {
+            return Err(Error::Solve("there is no AAA node to start from".to_string()));
+        }
+        let mut steps: u32 = 0;
+        let mut next_step = "AAA";
+
+        // Never ends if ZZZ can't be reached, so this relies on being cancelled
+        let token = cancel::token();
+        for direction in directions.iter().cycle() {
+            token.check()?;
+            steps += 1;
+
+            // Parsing checked every target is a node
+            let (left_choice, right_choice) = map[next_step];
+            match direction {
+                Direction::Left => next_step = left_choice,
+                Direction::Right => next_step = right_choice,
+            }
+            if next_step == "ZZZ" {
+                break;
+            }
+        }
+        Ok(steps)
+    }
+
+    fn part2(network: &Network) -> Result<usize, Error> {
+        let Network { directions, map } = network;
+
+        let mut steps: usize = 0;
+        let mut points: Vec<(&str, Option<usize>)> = map
+            .keys()
+            .filter(|k| k.ends_with('A'))
+            .copied()
+            .map(|k| (k, None))
+            .collect();
+        if points.is_empty() {
+            return Err(Error::Solve("no ghosts start on a ..A node".to_string()));
+        }
+
+        let token = cancel::token();
+        for direction in directions.iter().cycle() {
+            token.check()?;
+            steps += 1;
+            for (point, cycle) in points.iter_mut() {
+                let (left_choice, right_choice) = map[point];
+                match direction {
+                    Direction::Left => *point = left_choice,
+                    Direction::Right => *point = right_choice,
+                }
+                // Later visits are multiples of the first, which is the cycle length
+                if point.ends_with('Z') && cycle.is_none() {
+                    *cycle = Some(steps);
+                }
+            }
+            if points.iter().all(|(_, cycle)| cycle.is_some()) {
+                break;
+            }
+        }
+        let cycles = points.iter().map(|(_, steps)| steps.unwrap());
+        let overflow = || Error::Solve("the ghosts only line up after usize::MAX steps".to_string());
+        math::lcm_all(cycles).ok_or_else(overflow)
+    }
+
+    /// A network with `size` directions and up to six ghosts.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let ghosts = rng.range(1..=6);
+        Some(generate_network(rng, ghosts, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_puzzles, Part, RunOptions};
+    use std::time::Duration;
+
+    #[test]
+    fn no_ghosts_test() {
+        let input = "L\n\nAAB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        assert_eq!(
+            Day8::solve_part2(input),
+            Err(Error::Solve("no ghosts start on a ..A node".to_string()))
+        );
+    }
+
+    #[test]
+    fn missing_node_test() {
+        let input = "LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, CCC)\nZZZ = (ZZZ, ZZZ)\n";
+        let err = Day8::parse(input).unwrap_err().locate(input);
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 13: expected a node defined in the network, found 'CCC'"
+        );
+        let input = include_str!("../../example/day8_3.txt");
+        assert_eq!(
+            Day8::solve_part1(input),
+            Err(Error::Solve("there is no AAA node to start from".to_string()))
+        );
+    }
+
+    /// The first ghost passes its Z twice before the second reaches its own.
+    const UNEVEN_CYCLES: &str = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11B, 11B)
+22A = (22B, 22B)
+22B = (22C, 22C)
+22C = (22D, 22D)
+22D = (22E, 22E)
+22E = (22Z, 22Z)
+22Z = (22B, 22B)
+";
+
+    #[test]
+    fn part_2_uneven_cycles_test() {
+        assert_eq!(Day8::solve_part2(UNEVEN_CYCLES), Ok(10));
+    }
+
+    /// ZZZ can't be reached from AAA.
+    const UNREACHABLE: &str = "L
+
+AAA = (BBB, BBB)
+BBB = (AAA, AAA)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    #[test]
+    fn timeout_test() {
+        let limit = Duration::from_millis(20);
+        let options = RunOptions {
+            part: Some(Part::One),
+            timeout: Some(limit),
+            ..RunOptions::default()
+        };
+        let result = solve_puzzles::<Day8>(UNREACHABLE, &options);
+        assert_eq!(result.parts[0].outcome, Err(Error::TimedOut(limit)));
+    }
+
+    #[test]
+    fn generate_network_test() {
+        for seed in 0..10 {
+            let mut rng = Rng::new(seed);
+            let lone = generate_network(&mut rng, 1, 7);
+            let steps = Day8::solve_part1(&lone).unwrap();
+            assert_eq!(steps % 7, 0);
+            assert_eq!(Day8::solve_part2(&lone), Ok(steps as usize));
+            let network = generate_network(&mut rng, 4, 5);
+            assert_eq!(Day8::solve_part2(&network).unwrap() % 5, 0);
+        }
+    }
+
+    /// The most ghosts and directions still finish quickly and give every node its own name.
+    #[test]
+    fn generate_network_clamps_test() {
+        for (ghosts, directions) in [(20, 1000), (usize::MAX, usize::MAX), (6, 1000)] {
+            let input = generate_network(&mut Rng::new(1), ghosts, directions);
+            let network = Day8::parse(&input).unwrap();
+            // A repeated name would have replaced an earlier node in the map
+            assert_eq!(network.map.len(), input.lines().skip(2).count());
+            assert!(Day8::part2(&network).is_ok());
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use crate::gen::Rng;
+use crate::scan::Scanner;
+use crate::{Error, ParseError, Solution};
+
+/// How many values each generated history has, like the puzzle's.
+const GENERATED_LENGTH: usize = 21;
+
+enum Direction {
+    Forward,
+    Backward,
+}
+
+fn generate_differences(values: Vec<isize>) -> Vec<Vec<isize>> {
+    let mut differences = vec![values];
+    while !differences.last().unwrap().iter().all(|v| *v == 0isize) {
+        differences.push(
+            differences
+                .last()
+                .unwrap()
+                .windows(2)
+                .map(|w| w[1] - w[0])
+                .collect(),
+        );
+    }
+    differences
+}
+
+fn extrapolate_from_differences(differences: Vec<Vec<isize>>, direction: Direction) -> isize {
+    differences
+        .into_iter()
+        .rev()
+        .skip(1)
+        .fold(0isize, |acc, v| match direction {
+            Direction::Forward => v.last().unwrap() + acc,
+            Direction::Backward => v.first().unwrap() - acc,
+        })
+}
+
+/// Evaluates the polynomial with `coefficients`, lowest power first, at `x`.
+fn evaluate(coefficients: &[isize], x: isize) -> isize {
+    coefficients.iter().rev().fold(0, |acc, c| acc * x + c)
+}
+
+/// `count` histories of 21 values, each the values of a random polynomial of exactly `degree` at
+/// 0 to 20, with coefficients between -9 and 9. `degree` is capped at 8 to keep values in range.
+pub fn generate_sequences(rng: &mut Rng, count: usize, degree: usize) -> String {
+    let degree = degree.min(8);
+    let mut input = String::new();
+    for _ in 0..count {
+        let mut coefficients: Vec<isize> = (0..=degree).map(|_| rng.signed(-9..=9)).collect();
+        if coefficients[degree] == 0 {
+            coefficients[degree] = 1;
+        }
+        let values: Vec<String> = (0..GENERATED_LENGTH as isize)
+            .map(|x| evaluate(&coefficients, x).to_string())
+            .collect();
+        input.push_str(&values.join(" "));
+        input.push('\n');
+    }
+    input
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Parsed<'a> = Vec<Vec<isize>>;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> Result<Vec<Vec<isize>>, ParseError> {
+        Scanner::new(input)
+            .lines()
+            .map(|mut line| {
+                let mut history = Vec::new();
+                while !line.is_empty() {
+                    history.push(line.signed()?);
+                }
+                Ok(history)
+            })
+            .collect()
+    }
+
+    fn part1(histories: &Vec<Vec<isize>>) -> Result<isize, Error> {
+        Ok(histories
+            .iter()
+            .cloned()
+            .map(generate_differences)
+            .map(|d| extrapolate_from_differences(d, Direction::Forward))
+            .sum())
+    }
+
+    fn part2(histories: &Vec<Vec<isize>>) -> Result<isize, Error> {
+        Ok(histories
+            .iter()
+            .cloned()
+            .map(generate_differences)
+            .map(|d| extrapolate_from_differences(d, Direction::Backward))
+            .sum())
+    }
+
+    /// `size` histories, each of a random degree up to 5.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size {
+            let degree = rng.range(0..=5);
+            input.push_str(&generate_sequences(rng, 1, degree));
+        }
+        Some(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_differences_test() {
+        assert_eq!(
+            generate_differences(vec![1, 3, 6, 10, 15, 21],),
+            vec![
+                vec![1, 3, 6, 10, 15, 21],
+                vec![2, 3, 4, 5, 6],
+                vec![1, 1, 1, 1],
+                vec![0, 0, 0]
+            ]
+        );
+    }
+    #[test]
+    fn extrapolate_from_differences_test() {
+        assert_eq!(
+            extrapolate_from_differences(
+                generate_differences(vec![1, 3, 6, 10, 15, 21]),
+                Direction::Forward
+            ),
+            28
+        );
+    }
+
+    #[test]
+    fn generate_sequences_test() {
+        let mut rng = Rng::new(9);
+        for degree in 0..=8 {
+            let input = generate_sequences(&mut rng, 3, degree);
+            for history in Day9::parse(&input).unwrap() {
+                assert_eq!(history.len(), GENERATED_LENGTH);
+                // A polynomial of degree d needs d differences before they're constant
+                assert_eq!(generate_differences(history).len(), degree + 2);
+            }
+        }
+        assert_eq!(evaluate(&[1, -2, 3], 2), 9);
+    }
+}
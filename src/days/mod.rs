@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+use std::sync::mpsc;
+use std::thread;
+
+use rayon::prelude::*;
+
+use crate::compare::Comparison;
+use crate::{DayResult, Error, RunOptions, Solution};
+
+/// A registered puzzle day.
+pub struct Day {
+    pub number: u8,
+    solve: fn(&str, &RunOptions) -> DayResult,
+    compare: fn(&str, &RunOptions) -> Result<Vec<Comparison>, Error>,
+    generate: fn(u64, usize) -> Option<String>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>() -> Self {
+        Day {
+            number: S::DAY,
+            solve: crate::solve_puzzles::<S>,
+            compare: crate::compare::compare::<S>,
+            generate: crate::gen::generate::<S>,
+        }
+    }
+
+    pub fn solve(&self, input: &str, options: &RunOptions) -> DayResult {
+        (self.solve)(input, options)
+    }
+
+    /// Runs every implementation of each part, see [`crate::compare::compare`].
+    pub fn compare(&self, input: &str, options: &RunOptions) -> Result<Vec<Comparison>, Error> {
+        (self.compare)(input, options)
+    }
+
+    /// A random input from `seed`, see [`crate::gen::generate`].
+    pub fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        (self.generate)(seed, size)
+    }
+}
+
+macro_rules! register {
+    ($($module:ident => $solution:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        /// Every implemented day, in day order.
+        pub const DAYS: &[Day] = &[$(Day::new::<$module::$solution>()),*];
+
+        /// One test per day checking it against `answers/dayN.toml`, when that and the input exist.
+        #[cfg(test)]
+        mod regression_tests {
+            $(
+                #[test]
+                fn $module() {
+                    crate::answers::regression_test::<super::$module::$solution>();
+                }
+            )*
+        }
+    };
+}
+
+// Adding a day: write `src/days/dayN.rs` and add a line here.
+register! {
+    day1 => Day1,
+    day2 => Day2,
+    day3 => Day3,
+    day4 => Day4,
+    day5 => Day5,
+    day6 => Day6,
+    day7 => Day7,
+    day8 => Day8,
+    day9 => Day9,
+}
+
+pub fn get(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// Resolves a day selection such as `5`, `all` or `3..7` (inclusive) to registered days.
+pub fn select(selection: &str) -> Result<Vec<&'static Day>, String> {
+    if selection == "all" {
+        return Ok(DAYS.iter().collect());
+    }
+    let parse_day = |str: &str| {
+        str.parse::<u8>()
+            .map_err(|_| format!("invalid day '{str}'"))
+    };
+    if let Some((start, end)) = selection.split_once("..") {
+        let start = parse_day(start)?;
+        let end = parse_day(end.strip_prefix('=').unwrap_or(end))?;
+        if start > end {
+            return Err(format!("empty day range '{selection}'"));
+        }
+        let days: Vec<_> = DAYS
+            .iter()
+            .filter(|day| (start..=end).contains(&day.number))
+            .collect();
+        if days.is_empty() {
+            return Err(format!("no days implemented in range '{selection}'"));
+        }
+        return Ok(days);
+    }
+    let number = parse_day(selection)?;
+    get(number)
+        .map(|day| vec![day])
+        .ok_or_else(|| format!("day {number} is not implemented"))
+}
+
+/// Solves each of `days`, handing the results to `report` in day order. With `parallel`, days are
+/// solved concurrently on the rayon pool and each is reported as soon as it and every day before it
+/// are done.
+pub fn run_in_order<T: Send, E>(
+    days: &[&'static Day],
+    parallel: bool,
+    solve: impl Fn(&'static Day) -> T + Sync,
+    mut report: impl FnMut(T) -> Result<(), E>,
+) -> Result<(), E> {
+    if !parallel {
+        for day in days {
+            report(solve(day))?;
+        }
+        return Ok(());
+    }
+    let (sender, receiver) = mpsc::channel();
+    let solve = &solve;
+    thread::scope(|scope| {
+        // Off the main thread, so results can be reported while later days are still running
+        scope.spawn(move || {
+            days.par_iter()
+                .enumerate()
+                .for_each_with(sender, |sender, (index, day)| {
+                    // Only fails once reporting has failed, when the results aren't wanted
+                    let _ = sender.send((index, solve(day)));
+                });
+        });
+        let mut finished = BTreeMap::new();
+        let mut next = 0;
+        for (index, result) in &receiver {
+            finished.insert(index, result);
+            while let Some(result) = finished.remove(&next) {
+                report(result)?;
+                next += 1;
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(days: Vec<&Day>) -> Vec<u8> {
+        days.iter().map(|day| day.number).collect()
+    }
+
+    #[test]
+    fn run_in_order_test() {
+        let all = select("all").unwrap();
+        for parallel in [false, true] {
+            let mut reported = Vec::new();
+            let solve = |day: &Day| {
+                // Later days finish first
+                thread::sleep(std::time::Duration::from_millis(30 - day.number as u64));
+                day.number
+            };
+            run_in_order(&all, parallel, solve, |number| {
+                reported.push(number);
+                Ok::<_, ()>(())
+            })
+            .unwrap();
+            assert_eq!(reported, numbers(all.clone()));
+        }
+    }
+
+    #[test]
+    fn select_test() {
+        assert_eq!(numbers(select("5").unwrap()), vec![5]);
+        assert_eq!(numbers(select("3..7").unwrap()), vec![3, 4, 5, 6, 7]);
+        assert_eq!(numbers(select("8..=9").unwrap()), vec![8, 9]);
+        assert_eq!(select("all").unwrap().len(), DAYS.len());
+        assert!(select("25").is_err());
+        assert!(select("7..3").is_err());
+        assert!(select("x").is_err());
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::bench::InconsistentAnswer;
+
+/// Where a parse error sits in the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Position {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// Characters of the line covered by the error, at least 1.
+    pub width: usize,
+    /// The whole offending line, for printing a snippet.
+    pub source: String,
+}
+
+/// Input that didn't match what a parser expected.
+///
+/// Errors are built from the offending slice of the input. Because that slice points into the
+/// original input, [`ParseError::locate`] can later work out its line and column without every
+/// parser having to track them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<u8>,
+    pub expected: String,
+    pub found: String,
+    pub position: Option<Position>,
+    /// Address range of the offending slice, resolved against the input by `locate`.
+    span: (usize, usize),
+}
+
+impl ParseError {
+    /// An error at `span`, which must be a slice of the puzzle input.
+    pub fn new(span: &str, expected: impl Into<String>) -> Self {
+        let start = span.as_ptr() as usize;
+        ParseError {
+            day: None,
+            expected: expected.into(),
+            found: match span {
+                "" => "end of line".to_string(),
+                span => format!("'{span}'"),
+            },
+            position: None,
+            span: (start, start + span.len()),
+        }
+    }
+
+    /// An error for something missing after `span`.
+    pub fn after(span: &str, expected: impl Into<String>) -> Self {
+        ParseError::new(&span[span.len()..], expected)
+    }
+
+    /// Replaces the description of what was found, keeping the position.
+    pub fn found(mut self, found: impl Into<String>) -> Self {
+        self.found = found.into();
+        self
+    }
+
+    pub fn for_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Resolves the line and column of the error, if it points into `input`.
+    pub fn locate(mut self, input: &str) -> Self {
+        let input_start = input.as_ptr() as usize;
+        let (start, end) = self.span;
+        if start < input_start || end > input_start + input.len() {
+            return self;
+        }
+        let offset = start - input_start;
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let source = input[line_start..line_end].trim_end_matches('\r');
+        let span_end = (end - input_start).min(line_start + source.len());
+        self.position = Some(Position {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            width: input[offset..span_end.max(offset)].chars().count().max(1),
+            source: source.to_string(),
+        });
+        self
+    }
+
+    /// The offending line with a caret under the error, if it has been located.
+    pub fn snippet(&self) -> Option<String> {
+        let position = self.position.as_ref()?;
+        let gutter = position.line.to_string().len();
+        Some(format!(
+            "{:gutter$} |\n{} | {}\n{:gutter$} | {}{}",
+            "",
+            position.line,
+            position.source,
+            "",
+            " ".repeat(position.column - 1),
+            "^".repeat(position.width),
+        ))
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        if let Some(position) = &self.position {
+            write!(f, "line {}, column {}: ", position.line, position.column)?;
+        }
+        write!(f, "expected {}, found {}", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `span` as a number, or reports it as the offending input.
+pub fn parse_number<T: FromStr>(span: &str) -> Result<T, ParseError> {
+    span.parse().map_err(|_| ParseError::new(span, "a number"))
+}
+
+/// Why a phase failed to produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    /// The input parsed, but has no answer.
+    Solve(String),
+    Inconsistent(InconsistentAnswer),
+    /// The solver panicked. `location` is only known when the hook in `isolate` is installed.
+    Panic {
+        message: String,
+        location: Option<String>,
+    },
+    /// The solver gave up because its cancellation token was cancelled.
+    Cancelled,
+    /// The part ran for longer than `--timeout`.
+    TimedOut(Duration),
+}
+
+impl Error {
+    /// Resolves parse error positions against `input` and tags them with `day`.
+    pub fn locate(self, day: u8, input: &str) -> Self {
+        match self {
+            Error::Parse(err) => Error::Parse(err.for_day(day).locate(input)),
+            err => err,
+        }
+    }
+
+    /// A caret-annotated snippet of the input, for errors that have one.
+    pub fn snippet(&self) -> Option<String> {
+        match self {
+            Error::Parse(err) => err.snippet(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "{err}"),
+            Error::Solve(message) => write!(f, "{message}"),
+            Error::Inconsistent(err) => write!(f, "{err}"),
+            Error::Panic {
+                message,
+                location: Some(location),
+            } => write!(f, "panicked at {location}: {message}"),
+            Error::Panic {
+                message,
+                location: None,
+            } => write!(f, "panicked: {message}"),
+            Error::Cancelled => write!(f, "cancelled"),
+            Error::TimedOut(limit) => write!(f, "timed out after {limit:.2?}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl From<InconsistentAnswer> for Error {
+    fn from(err: InconsistentAnswer) -> Self {
+        Error::Inconsistent(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Game 1: 3 blue\nGame 2: 8 purple, 1 red\n";
+
+    #[test]
+    fn locate_test() {
+        let span = &INPUT[25..31];
+        assert_eq!(span, "purple");
+        let err = ParseError::new(span, "a colour").for_day(2).locate(INPUT);
+        assert_eq!(
+            err.position,
+            Some(Position {
+                line: 2,
+                column: 11,
+                width: 6,
+                source: "Game 2: 8 purple, 1 red".to_string(),
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "day 2, line 2, column 11: expected a colour, found 'purple'"
+        );
+        assert_eq!(
+            err.snippet().unwrap(),
+            "  |\n2 | Game 2: 8 purple, 1 red\n  |           ^^^^^^"
+        );
+    }
+
+    #[test]
+    fn after_test() {
+        let line = &INPUT[..14];
+        let err = ParseError::after(line, "','").locate(INPUT);
+        assert_eq!(err.found, "end of line");
+        assert_eq!(err.position.as_ref().unwrap().column, 15);
+        assert!(err.snippet().unwrap().ends_with("\n  |               ^"));
+    }
+
+    #[test]
+    fn locate_outside_input_test() {
+        let other = String::from("purple");
+        let err = ParseError::new(&other, "a colour").locate(INPUT);
+        assert_eq!(err.position, None);
+        assert_eq!(err.snippet(), None);
+    }
+
+    #[test]
+    fn parse_number_test() {
+        assert_eq!(parse_number::<u32>("42"), Ok(42));
+        assert_eq!(parse_number::<u32>("4x").unwrap_err().found, "'4x'");
+    }
+}
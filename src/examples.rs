@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use crate::manifest::{self, Entry, MANIFEST_PATH};
+use crate::{days, RunOptions};
+
+const EXAMPLE_DIR: &str = "example";
+
+/// Reads every entry in the example manifest.
+pub fn load() -> Result<Vec<Entry>, String> {
+    let contents = std::fs::read_to_string(MANIFEST_PATH)
+        .map_err(|err| format!("could not read {MANIFEST_PATH}: {err}"))?;
+    manifest::parse(&contents).map_err(|err| format!("{MANIFEST_PATH}: {err}"))
+}
+
+/// Runs an example through its day's solver, returning the answer it gave.
+pub fn run(entry: &Entry) -> Result<String, String> {
+    let day = days::get(entry.day).ok_or(format!("day {} is not implemented", entry.day))?;
+    let path = Path::new(EXAMPLE_DIR).join(&entry.file);
+    let input = std::fs::read_to_string(&path)
+        .map_err(|err| format!("could not read {}: {err}", path.display()))?;
+    let options = RunOptions {
+        part: Some(entry.part),
+        ..Default::default()
+    };
+    let result = day.solve(&input, &options);
+    result.parse.map_err(|err| err.to_string())?;
+    match result.parts.into_iter().next() {
+        Some(part) => part.outcome.map_err(|err| err.to_string()),
+        None => Err(format!("day {} part {} did not run", entry.day, entry.part)),
+    }
+}
+
+/// Runs an example and checks it gives the expected answer.
+pub fn check(entry: &Entry) -> Result<(), String> {
+    match run(entry)? {
+        answer if answer == entry.expected => Ok(()),
+        answer => Err(format!("expected {}, got {answer}", entry.expected)),
+    }
+}
+
+/// Called by the tests generated from the manifest by `build.rs`.
+#[cfg(test)]
+fn assert_example(day: u8, part: crate::Part, file: &str, expected: &str) {
+    let entry = Entry {
+        day,
+        part,
+        file: file.to_string(),
+        expected: expected.to_string(),
+    };
+    if let Err(err) = check(&entry) {
+        panic!("day {day} part {part} with {file}: {err}");
+    }
+}
+
+#[cfg(test)]
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Part;
+
+    #[test]
+    fn parse_test() {
+        let entries = manifest::parse("# comment\n8 1 day8_2.txt 6\n\n").unwrap();
+        assert_eq!(
+            entries,
+            vec![Entry {
+                day: 8,
+                part: Part::One,
+                file: "day8_2.txt".to_string(),
+                expected: "6".to_string(),
+            }]
+        );
+        assert_eq!(entries[0].name(), "day8_part1_day8_2");
+        assert!(manifest::parse("8 3 day8_2.txt 6").is_err());
+        assert!(manifest::parse("8 1 day8_2.txt").is_err());
+        assert_eq!(
+            manifest::parse("8 1 day8_2.txt 6\n8 2 day8_2.txt 6\n8 1 day8-2.txt 2"),
+            Err("line 3: day 8 part 1 with 'day8-2.txt' is already listed on line 1".to_string())
+        );
+    }
+
+    #[test]
+    fn check_test() {
+        let mut entry = manifest::parse("9 2 day9.txt 2").unwrap().remove(0);
+        assert_eq!(check(&entry), Ok(()));
+        entry.expected = "3".to_string();
+        assert_eq!(check(&entry), Err("expected 3, got 2".to_string()));
+        entry.file = "missing.txt".to_string();
+        assert!(check(&entry).unwrap_err().contains("missing.txt"));
+    }
+
+    #[test]
+    fn manifest_test() {
+        // Every registered day has at least one example for each part
+        let entries = load().unwrap();
+        for day in days::DAYS {
+            for part in [Part::One, Part::Two] {
+                assert!(
+                    entries
+                        .iter()
+                        .any(|entry| entry.day == day.number && entry.part == part),
+                    "no example for day {} part {part}",
+                    day.number
+                );
+            }
+        }
+    }
+}
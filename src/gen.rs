@@ -0,0 +1,106 @@
+use std::ops::RangeInclusive;
+
+use crate::Solution;
+
+/// The size `aoc gen` uses when `--size` isn't given.
+pub const DEFAULT_SIZE: usize = 10;
+
+/// A small seeded pseudo-random number generator (SplitMix64), so generated inputs are the same
+/// for the same seed on every machine.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {start}..={end}");
+        match (end - start).checked_add(1) {
+            Some(len) => start + (self.next_u64() % len as u64) as usize,
+            None => self.next_u64() as usize,
+        }
+    }
+
+    /// A signed number in `range`, which must not be empty.
+    pub fn signed(&mut self, range: RangeInclusive<isize>) -> isize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {start}..={end}");
+        start + self.range(0..=end.abs_diff(start)) as isize
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= p
+    }
+
+    /// A random element of `items`, which must not be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+/// A random puzzle input for `S` from `seed`, or `None` if the day has no generator. What `size`
+/// controls depends on the day, see [`Solution::generate`].
+pub fn generate<S: Solution>(seed: u64, size: usize) -> Option<String> {
+    S::generate(&mut Rng::new(seed), size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::DAYS;
+    use crate::RunOptions;
+
+    #[test]
+    fn rng_test() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let a: Vec<_> = (0..10).map(|_| a.range(1..=6)).collect();
+        let b: Vec<_> = (0..10).map(|_| b.range(1..=6)).collect();
+        assert_eq!(a, b);
+        assert!(a.iter().all(|n| (1..=6).contains(n)));
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+        let mut rng = Rng::new(7);
+        assert!((0..100).all(|_| (-3..=3).contains(&rng.signed(-3..=3))));
+        assert_eq!(rng.range(5..=5), 5);
+    }
+
+    /// Every day's generated inputs parse and solve without errors, and depend only on the seed.
+    #[test]
+    fn generated_inputs_solve_test() {
+        for day in DAYS {
+            for seed in 0..5 {
+                let input = day.generate(seed, DEFAULT_SIZE);
+                assert!(input.is_some(), "day {} has no generator", day.number);
+                assert_eq!(input, day.generate(seed, DEFAULT_SIZE));
+                let result = day.solve(&input.unwrap(), &RunOptions::default());
+                assert!(
+                    !result.failed(),
+                    "day {} seed {seed}: {:?}",
+                    day.number,
+                    result
+                );
+            }
+        }
+    }
+}
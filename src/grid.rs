@@ -0,0 +1,415 @@
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+use crate::ParseError;
+
+/// A position on a grid, or an offset between two. `y` grows downwards, like the lines of input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+    pub const UP: Point = Point::new(0, -1);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+    pub const RIGHT: Point = Point::new(1, 0);
+    /// The four orthogonal directions, clockwise from up.
+    pub const ORTHOGONAL: [Point; 4] = [Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT];
+    /// All eight directions, clockwise from up.
+    pub const ALL_DIRECTIONS: [Point; 8] = [
+        Point::UP,
+        Point::new(1, -1),
+        Point::RIGHT,
+        Point::new(1, 1),
+        Point::DOWN,
+        Point::new(-1, 1),
+        Point::LEFT,
+        Point::new(-1, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    /// The four points above, right of, below and left of this one.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Point::ORTHOGONAL
+            .into_iter()
+            .map(move |offset| self + offset)
+    }
+
+    /// The eight points around this one, including diagonals.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Point::ALL_DIRECTIONS
+            .into_iter()
+            .map(move |offset| self + offset)
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, scale: i64) -> Point {
+        Point::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+/// A rectangle of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid from its cells, row by row. Panics if there aren't `width * height` of them.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {width}x{height} grid needs {} cells",
+            width * height
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// A grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses one row per line, turning each character into a cell with `cell`.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, ParseError> {
+        Grid::try_parse(input, |c| Some(cell(c)), "")
+    }
+
+    /// Parses one row per line like [`Grid::parse`], for cells that can be invalid. A character
+    /// `cell` gives `None` for is reported as not being `expected`.
+    pub fn try_parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.lines() {
+            let mut row_width = 0;
+            for (i, c) in line.char_indices() {
+                let span = &line[i..i + c.len_utf8()];
+                cells.push(cell(c).ok_or_else(|| ParseError::new(span, expected))?);
+                row_width += 1;
+            }
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(ParseError::new(line, format!("a row of {width} cells"))
+                        .found(format!("{row_width} cells")));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    /// The cell at `point`, or `None` if it's outside the grid.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i64;
+        (0..self.height as i64).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The neighbours of `point` above, right, below and left that are inside the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .neighbours4()
+            .filter_map(|neighbour| Some((neighbour, self.get(neighbour)?)))
+    }
+
+    /// The neighbours of `point`, including diagonals, that are inside the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .neighbours8()
+            .filter_map(|neighbour| Some((neighbour, self.get(neighbour)?)))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// The rows from top to bottom. An empty grid has none.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    /// The cells of column `x` from top to bottom, or none if it's outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = match x < self.width {
+            true => &self.cells[x..],
+            false => &[],
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// The columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// The grid with rows and columns swapped, mirroring it along the diagonal from the top left.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flatten().cloned().collect();
+        Grid::new(self.height, self.width, cells)
+    }
+
+    /// The grid turned a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self
+            .columns()
+            .flat_map(|column| column.collect::<Vec<_>>().into_iter().rev())
+            .cloned()
+            .collect();
+        Grid::new(self.height, self.width, cells)
+    }
+
+    /// The grid turned a quarter turn anticlockwise.
+    pub fn rotate_anticlockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x))
+            .cloned()
+            .collect();
+        Grid::new(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    /// Panics if `point` is outside the grid, see [`Grid::get`] for a checked version.
+    fn index(&self, point: Point) -> &T {
+        match self.offset(point) {
+            Some(offset) => &self.cells[offset],
+            None => panic!("{point} is outside a {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.offset(point) {
+            Some(offset) => &mut self.cells[offset],
+            None => panic!("{point} is outside a {width}x{height} grid"),
+        }
+    }
+}
+
+/// Writes one line per row, each cell written as it displays.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(INPUT, |c| c).unwrap()
+    }
+
+    #[test]
+    fn point_test() {
+        let point = Point::new(2, 3);
+        assert_eq!(point + Point::UP, Point::new(2, 2));
+        assert_eq!(point - Point::new(1, 1), Point::new(1, 2));
+        assert_eq!(Point::RIGHT * 3, Point::new(3, 0));
+        assert_eq!(-point, Point::new(-2, -3));
+        let mut moved = point;
+        moved += Point::LEFT;
+        moved -= Point::DOWN;
+        assert_eq!(moved, Point::new(1, 2));
+        assert_eq!(point.manhattan(Point::ORIGIN), 5);
+        assert_eq!(point.neighbours4().count(), 4);
+        assert_eq!(point.neighbours8().count(), 8);
+        assert!(!point.neighbours8().any(|neighbour| neighbour == point));
+    }
+
+    #[test]
+    fn parse_test() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.to_string(), INPUT);
+        assert_eq!(grid[Point::new(1, 1)], 'e');
+        let err = Grid::parse("ab\nc\n", |c| c).unwrap_err();
+        assert_eq!(err.expected, "a row of 2 cells");
+        assert_eq!(err.found, "1 cells");
+        let err = Grid::try_parse(".#\n#x\n", |c| (c != 'x').then_some(c), "'.' or '#'");
+        assert_eq!(err.unwrap_err().found, "'x'");
+        assert_eq!(Grid::parse("", |c| c).unwrap().width(), 0);
+    }
+
+    #[test]
+    fn get_test() {
+        let mut grid = grid();
+        assert_eq!(grid.get(Point::new(2, 0)), Some(&'c'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        *grid.get_mut(Point::ORIGIN).unwrap() = 'z';
+        grid[Point::new(2, 1)] = 'y';
+        assert_eq!(grid.to_string(), "zbc\ndey\n");
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let grid = grid();
+        let cells = |neighbours: Vec<(Point, &char)>| -> String {
+            neighbours.into_iter().map(|(_, c)| *c).collect()
+        };
+        assert_eq!(cells(grid.neighbours4(Point::ORIGIN).collect()), "bd");
+        assert_eq!(cells(grid.neighbours8(Point::ORIGIN).collect()), "bed");
+        assert_eq!(cells(grid.neighbours8(Point::new(1, 1)).collect()), "bcfda");
+    }
+
+    #[test]
+    fn rows_and_columns_test() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        assert_eq!(grid.row(2), None);
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.points().last(), Some(Point::new(2, 1)));
+        assert_eq!(grid.iter().nth(4), Some((Point::new(1, 1), &'e')));
+        assert_eq!(
+            grid.map(|c| c.to_ascii_uppercase()).to_string(),
+            "ABC\nDEF\n"
+        );
+    }
+
+    #[test]
+    fn transform_test() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+        let turned = (0..4).fold(grid.clone(), |grid, _| grid.rotate_clockwise());
+        assert_eq!(turned, grid);
+    }
+}
@@ -0,0 +1,356 @@
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+use crate::{DayResult, Timing};
+
+/// Overrides where benchmark history is kept.
+pub const HISTORY_PATH_VAR: &str = "AOC_HISTORY";
+const DEFAULT_HISTORY_PATH: &str = "history.tsv";
+const UNKNOWN: &str = "unknown";
+
+/// One benchmarked phase, as a line of `history.tsv`:
+/// `timestamp<TAB>commit<TAB>machine<TAB>day<TAB>phase<TAB>median_ns<TAB>runs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub commit: String,
+    pub machine: String,
+    pub day: u8,
+    /// `parse`, `1` or `2`.
+    pub phase: String,
+    pub median: Duration,
+    pub runs: usize,
+}
+
+impl Entry {
+    fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<_> = line.split('\t').collect();
+        let [timestamp, commit, machine, day, phase, median, runs] = fields[..] else {
+            return None;
+        };
+        Some(Entry {
+            timestamp: timestamp.parse().ok()?,
+            commit: commit.to_string(),
+            machine: machine.to_string(),
+            day: day.parse().ok()?,
+            phase: phase.to_string(),
+            median: Duration::from_nanos(median.parse().ok()?),
+            runs: runs.parse().ok()?,
+        })
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.commit,
+            self.machine,
+            self.day,
+            self.phase,
+            self.median.as_nanos(),
+            self.runs
+        )
+    }
+}
+
+/// Which earlier run to compare against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Baseline {
+    /// The latest run on this machine from a different commit.
+    Previous,
+    /// The latest run on this machine from a commit starting with this prefix.
+    Commit(String),
+}
+
+impl Baseline {
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "previous" => Baseline::Previous,
+            commit => Baseline::Commit(commit.to_string()),
+        }
+    }
+}
+
+/// How one phase's median changed since the baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub day: u8,
+    pub phase: String,
+    pub commit: String,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Change {
+    /// The change as a fraction of the baseline, positive when slower. `None` if the baseline took
+    /// no measurable time, so there's nothing to take a fraction of.
+    pub fn ratio(&self) -> Option<f64> {
+        match self.before.is_zero() {
+            true => None,
+            false => Some(self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0),
+        }
+    }
+
+    /// True if the phase got slower by more than `threshold`, e.g. `0.1` for 10%. Never true
+    /// against a zero baseline.
+    pub fn is_slowdown(&self, threshold: f64) -> bool {
+        self.ratio().is_some_and(|ratio| ratio > threshold)
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phase = match self.phase.as_str() {
+            "parse" => "parse".to_string(),
+            part => format!("part {part}"),
+        };
+        write!(
+            f,
+            "day {} {phase}: {:.2?} ({}) -> {:.2?}",
+            self.day, self.before, self.commit, self.after
+        )?;
+        match self.ratio() {
+            Some(ratio) => write!(f, " ({:+.1}%)", ratio * 100.0),
+            None => write!(f, " (no baseline time)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    pub entries: Vec<Entry>,
+}
+
+impl History {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut entries = Vec::new();
+        for (number, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = Entry::parse(line).ok_or(format!(
+                "line {}: invalid history entry '{line}'",
+                number + 1
+            ))?;
+            entries.push(entry);
+        }
+        Ok(History { entries })
+    }
+
+    pub fn path() -> PathBuf {
+        std::env::var_os(HISTORY_PATH_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_HISTORY_PATH))
+    }
+
+    /// Loads the history, which is empty if nothing has been benchmarked yet.
+    pub fn load() -> Result<Self, String> {
+        let path = History::path();
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                History::parse(&contents).map_err(|err| format!("{}: {err}", path.display()))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(format!("could not read {}: {err}", path.display())),
+        }
+    }
+
+    /// Appends `entries` to the history file.
+    pub fn append(&mut self, entries: Vec<Entry>) -> Result<(), String> {
+        let path = History::path();
+        let mut lines = String::new();
+        for entry in &entries {
+            lines.push_str(&format!("{entry}\n"));
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(lines.as_bytes()))
+            .map_err(|err| format!("could not write {}: {err}", path.display()))?;
+        self.entries.extend(entries);
+        Ok(())
+    }
+
+    /// The latest entry matching `baseline` for the same machine, day and phase as `entry`.
+    pub fn baseline(&self, entry: &Entry, baseline: &Baseline) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .filter(|old| {
+                old.machine == entry.machine && old.day == entry.day && old.phase == entry.phase
+            })
+            .filter(|old| match baseline {
+                Baseline::Previous => old.commit != entry.commit,
+                Baseline::Commit(commit) => old.commit.starts_with(commit.as_str()),
+            })
+            .max_by_key(|old| old.timestamp)
+    }
+
+    /// How each of `entries` compares to its baseline. Phases with no baseline are left out.
+    pub fn compare(&self, entries: &[Entry], baseline: &Baseline) -> Vec<Change> {
+        entries
+            .iter()
+            .filter_map(|entry| {
+                let old = self.baseline(entry, baseline)?;
+                Some(Change {
+                    day: entry.day,
+                    phase: entry.phase.clone(),
+                    commit: old.commit.clone(),
+                    before: old.median,
+                    after: entry.median,
+                })
+            })
+            .collect()
+    }
+}
+
+/// History entries for every benchmarked phase in `results`. Single timings are too noisy to keep.
+pub fn entries(results: &[DayResult], commit: &str, machine: &str) -> Vec<Entry> {
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    let entry = |day: u8, phase: String, timing: Option<&Timing>| match timing {
+        Some(Timing::Bench(stats)) => Some(Entry {
+            timestamp,
+            commit: commit.to_string(),
+            machine: machine.to_string(),
+            day,
+            phase,
+            median: stats.median,
+            runs: stats.runs,
+        }),
+        _ => None,
+    };
+    let mut entries = Vec::new();
+    for result in results {
+        entries.extend(entry(
+            result.day,
+            "parse".to_string(),
+            result.parse.as_ref().ok(),
+        ));
+        for part in &result.parts {
+            if part.outcome.is_ok() {
+                entries.extend(entry(
+                    result.day,
+                    part.part.to_string(),
+                    part.timing.as_ref(),
+                ));
+            }
+        }
+    }
+    entries
+}
+
+/// The short hash of the checked out commit, marked `-dirty` if there are uncommitted changes.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(status) if !status.is_empty() => format!("{commit}-dirty"),
+            _ => commit,
+        },
+        None => UNKNOWN.to_string(),
+    }
+}
+
+/// A name for this machine, so timings from different hardware aren't compared.
+pub fn machine_name() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| UNKNOWN.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HISTORY: &str = "\
+1701000000\tabc1234\tlaptop\t3\t2\t3600000\t250
+1701000100\tdef5678\tlaptop\t3\t2\t3500000\t260
+1701000200\tdef5678\tdesktop\t3\t2\t1000000\t900
+1701000300\t9876fed\tlaptop\t3\t2\t3400000\t270
+";
+
+    fn current(median_ms: u64) -> Entry {
+        Entry {
+            timestamp: 1701000400,
+            commit: "9876fed".to_string(),
+            machine: "laptop".to_string(),
+            day: 3,
+            phase: "2".to_string(),
+            median: Duration::from_millis(median_ms),
+            runs: 200,
+        }
+    }
+
+    #[test]
+    fn parse_test() {
+        let history = History::parse(HISTORY).unwrap();
+        assert_eq!(history.entries.len(), 4);
+        assert_eq!(history.entries[0].median, Duration::from_micros(3600));
+        assert_eq!(
+            history.entries[0].to_string(),
+            HISTORY.lines().next().unwrap()
+        );
+        assert!(History::parse("1701000000\tabc1234\tlaptop\t3\t2\t3600000\n").is_err());
+    }
+
+    #[test]
+    fn baseline_test() {
+        let history = History::parse(HISTORY).unwrap();
+        let entry = current(4);
+        let previous = history.baseline(&entry, &Baseline::Previous).unwrap();
+        assert_eq!(previous.commit, "def5678");
+        assert_eq!(previous.machine, "laptop");
+        let commit = Baseline::from_arg("abc");
+        assert_eq!(
+            history.baseline(&entry, &commit).unwrap().timestamp,
+            1701000000
+        );
+        assert_eq!(history.baseline(&entry, &Baseline::from_arg("0000")), None);
+    }
+
+    #[test]
+    fn compare_test() {
+        let history = History::parse(HISTORY).unwrap();
+        let changes = history.compare(&[current(4)], &Baseline::Previous);
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].is_slowdown(0.1));
+        assert!(!changes[0].is_slowdown(0.2));
+        assert_eq!(
+            changes[0].to_string(),
+            "day 3 part 2: 3.50ms (def5678) -> 4.00ms (+14.3%)"
+        );
+        let changes = history.compare(&[current(3)], &Baseline::Previous);
+        assert!(!changes[0].is_slowdown(0.1));
+
+        let change = Change {
+            before: Duration::ZERO,
+            ..changes[0].clone()
+        };
+        assert_eq!(change.ratio(), None);
+        assert!(!change.is_slowdown(0.1));
+        assert_eq!(
+            change.to_string(),
+            "day 3 part 2: 0.00ns (def5678) -> 3.00ms (no baseline time)"
+        );
+    }
+}
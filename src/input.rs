@@ -0,0 +1,112 @@
+use std::fmt::{self, Display};
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Overrides the directory puzzle inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+const DEFAULT_INPUT_DIR: &str = "input";
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `dayN.txt` in `$AOC_INPUT_DIR`, falling back to `input/`.
+    #[default]
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Interprets an `--input` argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { day: u8, path: PathBuf },
+    Read { path: PathBuf, source: io::Error },
+    Stdin(io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing { day, path } => write!(
+                f,
+                "no input for day {day}: {} does not exist\n\
+                 save your puzzle input there, pass --input <file>, or set {INPUT_DIR_VAR}",
+                path.display()
+            ),
+            InputError::Read { path, source } => {
+                write!(f, "could not read {}: {source}", path.display())
+            }
+            InputError::Stdin(source) => write!(f, "could not read input from stdin: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn input_dir() -> PathBuf {
+    std::env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
+
+pub fn default_path(day: u8) -> PathBuf {
+    input_dir().join(format!("day{day}.txt"))
+}
+
+pub fn load(day: u8, source: &InputSource) -> Result<String, InputError> {
+    match source {
+        InputSource::Default => read_file(day, default_path(day)),
+        InputSource::File(path) => read_file(day, path.clone()),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(InputError::Stdin)?;
+            Ok(input)
+        }
+    }
+}
+
+fn read_file(day: u8, path: PathBuf) -> Result<String, InputError> {
+    std::fs::read_to_string(&path).map_err(|source| match source.kind() {
+        io::ErrorKind::NotFound => InputError::Missing { day, path },
+        _ => InputError::Read { path, source },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_arg_test() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("my/day1.txt"),
+            InputSource::File(PathBuf::from("my/day1.txt"))
+        );
+    }
+
+    #[test]
+    fn missing_file_test() {
+        let source = InputSource::File(PathBuf::from("example/does_not_exist.txt"));
+        let err = load(1, &source).unwrap_err();
+        assert!(matches!(err, InputError::Missing { day: 1, .. }));
+        assert!(err.to_string().contains("example/does_not_exist.txt"));
+    }
+
+    #[test]
+    fn file_test() {
+        let source = InputSource::File(PathBuf::from("example/day2.txt"));
+        assert!(load(2, &source).unwrap().starts_with("Game 1:"));
+    }
+}
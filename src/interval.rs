@@ -0,0 +1,359 @@
+use std::ops::{Add, Range, Sub};
+
+/// A set of values, stored as sorted, disjoint half-open ranges. Ranges that overlap or touch are
+/// merged, so two sets with the same values always have the same ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// The set of values in any of `ranges`, which can be empty, unsorted or overlapping.
+    pub fn from_ranges(ranges: impl IntoIterator<Item = Range<T>>) -> Self {
+        let mut ranges: Vec<_> = ranges
+            .into_iter()
+            .filter(|range| range.start < range.end)
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if last.end >= range.start => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        IntervalSet { ranges: merged }
+    }
+
+    /// The ranges of the set, sorted and disjoint.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = self.union(&IntervalSet::from_ranges([range]));
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        // The first range that ends after `value` is the only one that can hold it
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start <= value)
+    }
+
+    /// True if every value of `range` is in the set. An empty range always is.
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.start >= range.end {
+            return true;
+        }
+        let index = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(index)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        IntervalSet::from_ranges(self.ranges.iter().chain(&other.ranges).cloned())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            // Whichever ends first can't overlap anything later in the other set
+            match a.end < b.end {
+                true => i += 1,
+                false => j += 1,
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// The values in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut removed = other.ranges.iter().peekable();
+        for range in &self.ranges {
+            let mut start = range.start;
+            while let Some(hole) = removed.peek() {
+                if hole.end <= start {
+                    removed.next();
+                    continue;
+                }
+                if hole.start >= range.end {
+                    break;
+                }
+                if hole.start > start {
+                    ranges.push(start..hole.start);
+                }
+                start = hole.end;
+                if hole.end > range.end {
+                    break;
+                }
+                removed.next();
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        IntervalSet::from_ranges(ranges)
+    }
+}
+
+/// Moves ranges of values by piecewise offsets: each source range is mapped onto a destination
+/// range of the same length, and values outside every source range map to themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalMap<T> {
+    /// Disjoint source ranges, sorted, with where each one starts mapping to.
+    entries: Vec<(Range<T>, T)>,
+    sources: IntervalSet<T>,
+}
+
+impl<T> Default for IntervalMap<T> {
+    fn default() -> Self {
+        IntervalMap {
+            entries: Vec::new(),
+            sources: IntervalSet::default(),
+        }
+    }
+}
+
+impl<T> IntervalMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        IntervalMap::default()
+    }
+
+    /// Maps `source` onto the range of the same length starting at `destination`. Where `source`
+    /// overlaps a range inserted earlier, the earlier one wins.
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        let added = IntervalSet::from_ranges([source.clone()]).difference(&self.sources);
+        for piece in added.ranges() {
+            self.entries
+                .push((piece.clone(), destination + (piece.start - source.start)));
+        }
+        self.entries.sort_by_key(|(piece, _)| piece.start);
+        self.sources = self.sources.union(&added);
+    }
+
+    pub fn get(&self, value: T) -> T {
+        let index = self
+            .entries
+            .partition_point(|(source, _)| source.end <= value);
+        match self.entries.get(index) {
+            Some((source, destination)) if source.start <= value => {
+                *destination + (value - source.start)
+            }
+            _ => value,
+        }
+    }
+
+    /// Where every value of `range` maps to, as ranges in the order of the values they came from.
+    pub fn map_range(&self, range: Range<T>) -> Vec<Range<T>> {
+        let mut pieces = Vec::new();
+        let mut start = range.start;
+        let first = self
+            .entries
+            .partition_point(|(source, _)| source.end <= start);
+        for (source, destination) in &self.entries[first..] {
+            if start >= range.end || source.start >= range.end {
+                break;
+            }
+            if source.start > start {
+                pieces.push(start..source.start);
+                start = source.start;
+            }
+            let end = source.end.min(range.end);
+            let offset = start - source.start;
+            pieces.push(*destination + offset..*destination + offset + (end - start));
+            start = end;
+        }
+        if start < range.end {
+            pieces.push(start..range.end);
+        }
+        pieces
+    }
+
+    /// Where every value of `set` maps to.
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.ranges()
+            .iter()
+            .flat_map(|range| self.map_range(range.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::Rng;
+    use std::collections::BTreeSet;
+
+    /// Values the property tests draw ranges from.
+    const DOMAIN: u32 = 40;
+
+    fn random_ranges(rng: &mut Rng) -> Vec<Range<u32>> {
+        (0..rng.range(0..=5))
+            .map(|_| {
+                let start = rng.range(0..=DOMAIN as usize) as u32;
+                start..rng.range(0..=DOMAIN as usize) as u32
+            })
+            .collect()
+    }
+
+    fn naive(ranges: &[Range<u32>]) -> BTreeSet<u32> {
+        ranges.iter().flat_map(|range| range.clone()).collect()
+    }
+
+    fn values(set: &IntervalSet<u32>) -> BTreeSet<u32> {
+        naive(set.ranges())
+    }
+
+    /// The ranges are sorted, non-empty and neither overlap nor touch.
+    fn assert_normalised(set: &IntervalSet<u32>) {
+        assert!(set.ranges().iter().all(|range| range.start < range.end));
+        assert!(set
+            .ranges()
+            .windows(2)
+            .all(|pair| pair[0].end < pair[1].start));
+    }
+
+    #[test]
+    fn from_ranges_test() {
+        let set = IntervalSet::from_ranges([5..8, 0..2, 2..3, 7..10, 4..4]);
+        assert_eq!(set.ranges(), [0..3, 5..10]);
+        assert_eq!(set.min(), Some(0));
+        assert!(IntervalSet::<u32>::new().is_empty());
+        let mut set: IntervalSet<u32> = [1..3, 7..8].into_iter().collect();
+        set.insert(3..5);
+        assert_eq!(set.ranges(), [1..5, 7..8]);
+    }
+
+    #[test]
+    fn contains_test() {
+        let set = IntervalSet::from_ranges([0..3, 5..10]);
+        assert!(set.contains(0) && set.contains(2) && set.contains(9));
+        assert!(!set.contains(3) && !set.contains(10));
+        assert!(set.contains_range(&(5..10)));
+        assert!(!set.contains_range(&(2..6)));
+        assert!(set.contains_range(&(4..4)));
+    }
+
+    #[test]
+    fn set_properties_test() {
+        let mut rng = Rng::new(23);
+        for _ in 0..2000 {
+            let (a, b) = (random_ranges(&mut rng), random_ranges(&mut rng));
+            let (set_a, set_b) = (
+                IntervalSet::from_ranges(a.clone()),
+                IntervalSet::from_ranges(b.clone()),
+            );
+            let (naive_a, naive_b) = (naive(&a), naive(&b));
+            assert_eq!(values(&set_a), naive_a);
+            assert_normalised(&set_a);
+
+            let union = set_a.union(&set_b);
+            assert_eq!(values(&union), &naive_a | &naive_b, "{a:?} | {b:?}");
+            assert_normalised(&union);
+            let intersection = set_a.intersection(&set_b);
+            assert_eq!(values(&intersection), &naive_a & &naive_b, "{a:?} & {b:?}");
+            assert_normalised(&intersection);
+            let difference = set_a.difference(&set_b);
+            assert_eq!(values(&difference), &naive_a - &naive_b, "{a:?} - {b:?}");
+            assert_normalised(&difference);
+
+            for value in 0..=DOMAIN {
+                assert_eq!(set_a.contains(value), naive_a.contains(&value));
+            }
+            for range in &b {
+                let expected = range.clone().all(|value| naive_a.contains(&value));
+                assert_eq!(set_a.contains_range(range), expected, "{a:?} ⊇ {range:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn map_test() {
+        let mut map = IntervalMap::new();
+        map.insert(98..100, 50);
+        map.insert(50..98, 52);
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(99), 51);
+        assert_eq!(map.get(100), 100);
+        assert_eq!(map.map_range(90..102), [92..100, 50..52, 100..102]);
+        // The earlier range wins where they overlap
+        map.insert(40..60, 0);
+        assert_eq!(map.get(45), 5);
+        assert_eq!(map.get(55), 57);
+        let seeds = IntervalSet::from_ranges([79..93, 55..68]);
+        assert_eq!(map.map_set(&seeds).ranges(), [57..70, 81..95]);
+    }
+
+    #[test]
+    fn map_properties_test() {
+        let mut rng = Rng::new(5);
+        for _ in 0..2000 {
+            let mut map = IntervalMap::new();
+            let mut entries = Vec::new();
+            for source in random_ranges(&mut rng) {
+                let destination = rng.range(0..=DOMAIN as usize) as u32;
+                map.insert(source.clone(), destination);
+                entries.push((source, destination));
+            }
+            // The first entry holding a value decides where it goes
+            let naive_get = |value: u32| {
+                entries
+                    .iter()
+                    .find(|(source, _)| source.contains(&value))
+                    .map_or(value, |(source, destination)| {
+                        destination + value - source.start
+                    })
+            };
+            for value in 0..=DOMAIN {
+                assert_eq!(map.get(value), naive_get(value), "{entries:?} at {value}");
+            }
+            let ranges = random_ranges(&mut rng);
+            for range in &ranges {
+                let mapped: Vec<u32> = map.map_range(range.clone()).into_iter().flatten().collect();
+                let expected: Vec<u32> = range.clone().map(naive_get).collect();
+                assert_eq!(mapped, expected, "{entries:?} on {range:?}");
+            }
+            let set = IntervalSet::from_ranges(ranges);
+            let expected: BTreeSet<u32> = values(&set).into_iter().map(naive_get).collect();
+            assert_eq!(values(&map.map_set(&set)), expected);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::Error;
+
+thread_local! {
+    /// Whether this thread is inside `catch`, so the hook knows to stay quiet.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Where the last caught panic happened, recorded by the hook.
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// How many `catch` calls are running on any thread.
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+/// Where the last panic on a rayon worker happened, while some `catch` was running. Rayon hands
+/// such panics on to the thread that started the parallel work, which is inside `catch` but never
+/// sees the hook run.
+static WORKER_LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+/// Stops panics inside `catch` from being printed as they happen, and records their location so it
+/// can be reported with the rest of the results. That includes panics in parallel work a caught
+/// solver hands to rayon. Panics anywhere else go to the previous hook.
+pub fn install_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let location = info.location().map(ToString::to_string);
+        if CATCHING.get() {
+            LOCATION.set(location);
+        } else if rayon::current_thread_index().is_some() && ACTIVE.load(Ordering::SeqCst) > 0 {
+            *WORKER_LOCATION
+                .lock()
+                .unwrap_or_else(|err| err.into_inner()) = location;
+        } else {
+            previous(info);
+        }
+    }));
+}
+
+fn message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Runs `f`, turning a panic into an [`Error::Panic`] instead of unwinding any further.
+pub fn catch<T>(f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    let outer = CATCHING.replace(true);
+    ACTIVE.fetch_add(1, Ordering::SeqCst);
+    LOCATION.take();
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    ACTIVE.fetch_sub(1, Ordering::SeqCst);
+    CATCHING.set(outer);
+    result.unwrap_or_else(|payload| {
+        // A panic on this thread sets LOCATION, one handed on from a rayon worker doesn't
+        let worker = || {
+            WORKER_LOCATION
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .take()
+        };
+        Err(Error::Panic {
+            message: message(&*payload),
+            location: LOCATION.take().or_else(worker),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catch_test() {
+        assert_eq!(catch(|| Ok(1)), Ok(1));
+        assert_eq!(
+            catch::<()>(|| Err(Error::Solve("no answer".to_string()))),
+            Err(Error::Solve("no answer".to_string()))
+        );
+        let Err(Error::Panic { message, .. }) = catch::<()>(|| panic!("node {} missing", "ZZZ"))
+        else {
+            panic!("expected a panic error");
+        };
+        assert_eq!(message, "node ZZZ missing");
+        assert!(!CATCHING.get());
+    }
+
+    /// A panic on a rayon worker is caught by the `catch` that started the parallel work, with
+    /// the worker's location.
+    #[test]
+    fn catch_parallel_test() {
+        use rayon::prelude::*;
+        install_hook();
+        let result = catch::<()>(|| {
+            (0..64).into_par_iter().for_each(|i| {
+                if i == 40 {
+                    panic!("seed {i} missing");
+                }
+            });
+            Ok(())
+        });
+        let Err(Error::Panic { message, location }) = result else {
+            panic!("expected a panic error");
+        };
+        assert_eq!(message, "seed 40 missing");
+        assert!(location.is_some_and(|location| location.starts_with("src/isolate.rs")));
+    }
+}
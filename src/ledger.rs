@@ -0,0 +1,231 @@
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::answers::answers_dir;
+use crate::client::Outcome;
+use crate::Part;
+
+/// Every answer submitted so far, one per line as `day<TAB>part<TAB>outcome<TAB>answer`.
+const LEDGER_FILE: &str = "submissions.tsv";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Why an answer shouldn't be submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Blocked {
+    Repeat(Outcome),
+    Solved {
+        answer: String,
+    },
+    /// At or above an answer that was too high.
+    TooHigh {
+        bound: String,
+    },
+    /// At or below an answer that was too low.
+    TooLow {
+        bound: String,
+    },
+}
+
+impl Display for Blocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Blocked::Repeat(outcome) => write!(f, "already submitted, and it was {outcome}"),
+            Blocked::Solved { answer } => write!(f, "already solved with {answer}"),
+            Blocked::TooHigh { bound } => write!(f, "{bound} was already too high"),
+            Blocked::TooLow { bound } => write!(f, "{bound} was already too low"),
+        }
+    }
+}
+
+fn outcome_name(outcome: &Outcome) -> Option<&'static str> {
+    match outcome {
+        Outcome::Correct => Some("correct"),
+        Outcome::Wrong => Some("wrong"),
+        Outcome::TooHigh => Some("too-high"),
+        Outcome::TooLow => Some("too-low"),
+        Outcome::RateLimited { .. } | Outcome::Unrecognised(_) => None,
+    }
+}
+
+fn parse_outcome(name: &str) -> Option<Outcome> {
+    match name {
+        "correct" => Some(Outcome::Correct),
+        "wrong" => Some(Outcome::Wrong),
+        "too-high" => Some(Outcome::TooHigh),
+        "too-low" => Some(Outcome::TooLow),
+        _ => None,
+    }
+}
+
+/// The submissions made so far, used to avoid wasting a submission on an answer already known
+/// to be wrong.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ledger {
+    pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut submissions = Vec::new();
+        for (number, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut fields = line.splitn(4, '\t');
+            let day = fields.next().and_then(|day| day.parse().ok());
+            let part = fields.next().and_then(Part::from_number);
+            let outcome = fields.next().and_then(parse_outcome);
+            let answer = fields.next();
+            let (Some(day), Some(part), Some(outcome), Some(answer)) = (day, part, outcome, answer)
+            else {
+                return Err(format!("line {}: invalid submission '{line}'", number + 1));
+            };
+            submissions.push(Submission {
+                day,
+                part,
+                answer: answer.to_string(),
+                outcome,
+            });
+        }
+        Ok(Ledger { submissions })
+    }
+
+    pub fn path() -> PathBuf {
+        answers_dir().join(LEDGER_FILE)
+    }
+
+    /// Loads the ledger, which is empty if nothing has been submitted yet.
+    pub fn load() -> Result<Self, String> {
+        let path = Ledger::path();
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                Ledger::parse(&contents).map_err(|err| format!("{}: {err}", path.display()))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(err) => Err(format!("could not read {}: {err}", path.display())),
+        }
+    }
+
+    /// Checks `answer`, which the command line has already trimmed, against everything already
+    /// learned about this part.
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Result<(), Blocked> {
+        let value = answer.parse::<i128>().ok();
+        let submissions = self
+            .submissions
+            .iter()
+            .filter(|submission| submission.day == day && submission.part == part);
+        for submission in submissions {
+            if submission.answer == answer {
+                return Err(Blocked::Repeat(submission.outcome.clone()));
+            }
+            let bound = submission.answer.parse::<i128>().ok();
+            match (&submission.outcome, value, bound) {
+                (Outcome::Correct, _, _) => {
+                    return Err(Blocked::Solved {
+                        answer: submission.answer.clone(),
+                    })
+                }
+                (Outcome::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                    return Err(Blocked::TooHigh {
+                        bound: submission.answer.clone(),
+                    })
+                }
+                (Outcome::TooLow, Some(value), Some(bound)) if value <= bound => {
+                    return Err(Blocked::TooLow {
+                        bound: submission.answer.clone(),
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a submission to the ledger file. Outcomes that say nothing about the answer, such as
+    /// being rate limited, aren't recorded.
+    pub fn record(&mut self, submission: Submission) -> Result<(), String> {
+        let Some(outcome) = outcome_name(&submission.outcome) else {
+            return Ok(());
+        };
+        let path = Ledger::path();
+        let line = format!(
+            "{}\t{}\t{outcome}\t{}\n",
+            submission.day, submission.part, submission.answer
+        );
+        let append = || {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)?
+                .write_all(line.as_bytes())
+        };
+        append().map_err(|err| format!("could not write {}: {err}", path.display()))?;
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEDGER: &str = "7\t2\ttoo-high\t250665248\n7\t2\ttoo-low\t250000000\n7\t2\twrong\t250500000\n7\t1\tcorrect\t6440\n";
+
+    #[test]
+    fn parse_test() {
+        let ledger = Ledger::parse(LEDGER).unwrap();
+        assert_eq!(ledger.submissions.len(), 4);
+        assert_eq!(
+            ledger.submissions[0],
+            Submission {
+                day: 7,
+                part: Part::Two,
+                answer: "250665248".to_string(),
+                outcome: Outcome::TooHigh,
+            }
+        );
+        assert!(Ledger::parse("7\t3\twrong\t1\n").is_err());
+        assert!(Ledger::parse("7\t2\tmaybe\t1\n").is_err());
+    }
+
+    #[test]
+    fn check_test() {
+        let ledger = Ledger::parse(LEDGER).unwrap();
+        assert_eq!(
+            ledger.check(7, Part::Two, "250500000"),
+            Err(Blocked::Repeat(Outcome::Wrong))
+        );
+        assert_eq!(
+            ledger.check(7, Part::Two, "250665249"),
+            Err(Blocked::TooHigh {
+                bound: "250665248".to_string()
+            })
+        );
+        assert_eq!(
+            ledger.check(7, Part::Two, "1"),
+            Err(Blocked::TooLow {
+                bound: "250000000".to_string()
+            })
+        );
+        assert_eq!(ledger.check(7, Part::Two, "250400000"), Ok(()));
+        assert_eq!(ledger.check(7, Part::Two, "abc"), Ok(()));
+        assert_eq!(
+            ledger.check(7, Part::One, "6441"),
+            Err(Blocked::Solved {
+                answer: "6440".to_string()
+            })
+        );
+        assert_eq!(ledger.check(8, Part::Two, "250665249"), Ok(()));
+    }
+}
@@ -1,27 +1,278 @@
-use std::fmt::{Debug, Display};
-use std::time::Instant;
-
-const ANSI_YELLOW_BOLD: &str = "\x1B[1;33m";
-const ANSI_GREEN_BOLD: &str = "\x1B[1;32m";
-const ANSI_WHITE_BOLD: &str = "\x1B[1;37m";
-const ANSI_RESET: &str = "\x1b[0m";
-const CHRISTMAS_CHEER: &str = "🎄⭐🎅🎄⭐🎅🎄⭐🎅🎄⭐🎅";
-
-pub fn solve_puzzles<T1: Display + Debug, T2: Display + Debug>(
-    input: &str,
-    part1: impl FnOnce(&str) -> T1,
-    part2: impl FnOnce(&str) -> T2,
-) {
-    let timer = Instant::now();
-    let pt1_result = part1(input);
-    let time = timer.elapsed();
-    println!("\n{CHRISTMAS_CHEER}");
-    println!("{ANSI_WHITE_BOLD}Part 1 Answer: \n{ANSI_GREEN_BOLD}{pt1_result}{ANSI_RESET}");
-    println!("Time taken: \n{ANSI_YELLOW_BOLD}{time:.2?}{ANSI_RESET}\n");
-    let timer = Instant::now();
-    let pt2_result = part2(input);
-    let time = timer.elapsed();
-    println!("{ANSI_WHITE_BOLD}Part 2 Answer: \n{ANSI_GREEN_BOLD}{pt2_result}{ANSI_RESET}");
-    println!("Time taken: \n{ANSI_YELLOW_BOLD}{time:.2?}{ANSI_RESET}");
-    println!("{CHRISTMAS_CHEER}\n");
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+use allocs::AllocStats;
+use answers::Verdict;
+use bench::{BenchConfig, Stats};
+use gen::Rng;
+
+pub mod allocs;
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod client;
+pub mod compare;
+pub mod days;
+pub mod error;
+pub mod examples;
+pub mod gen;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod interval;
+pub mod isolate;
+pub mod ledger;
+pub mod manifest;
+pub mod math;
+pub mod parse;
+mod part;
+pub mod readme;
+pub mod report;
+pub mod scaffold;
+pub mod scan;
+pub mod watch;
+
+pub use error::{Error, ParseError};
+pub use part::Part;
+pub use report::Reporter;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: allocs::Counting = allocs::Counting;
+
+/// A day's puzzle, split into a parse phase and two parts that share the parsed model.
+pub trait Solution {
+    const DAY: u8;
+    type Parsed<'a>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Self::Answer1, Error>;
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Self::Answer2, Error>;
+
+    /// Other implementations of each part, checked against `part1` and `part2` by `aoc compare`.
+    fn variants() -> Variants<Self> {
+        Variants::default()
+    }
+
+    /// A random valid input, for `aoc gen` and property tests, or `None` if the day has no
+    /// generator. `size` scales the input, e.g. the number of lines; what it controls is documented
+    /// on each day's implementation.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// Parses `input` and solves part 1, for tests.
+    fn solve_part1(input: &str) -> Result<Self::Answer1, Error> {
+        Self::part1(&Self::parse(input)?)
+    }
+
+    /// Parses `input` and solves part 2, for tests.
+    fn solve_part2(input: &str) -> Result<Self::Answer2, Error> {
+        Self::part2(&Self::parse(input)?)
+    }
+}
+
+/// A named implementation of a part, taking the parsed input like [`Solution::part1`].
+pub type Variant<S, A> = (
+    &'static str,
+    fn(&<S as Solution>::Parsed<'_>) -> Result<A, Error>,
+);
+
+/// Alternative implementations of a day's parts, such as a brute force kept next to a faster
+/// version. The default implementations are always compared too, so these only list the others.
+pub struct Variants<S: Solution + ?Sized> {
+    pub part1: Vec<Variant<S, S::Answer1>>,
+    pub part2: Vec<Variant<S, S::Answer2>>,
+}
+
+impl<S: Solution + ?Sized> Default for Variants<S> {
+    fn default() -> Self {
+        Variants {
+            part1: Vec::new(),
+            part2: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Only run this part, or both when `None`.
+    pub part: Option<Part>,
+    /// Repeat every phase and report statistics instead of a single timing.
+    pub bench: Option<BenchConfig>,
+    /// How long each part may run before it's cancelled and reported as timed out.
+    pub timeout: Option<Duration>,
+}
+
+/// How long a phase took: a single run, or statistics over many.
+#[derive(Debug, Clone, Copy)]
+pub enum Timing {
+    Once(Duration),
+    Bench(Stats),
+}
+
+impl Timing {
+    /// The single run time, or the median when benchmarking.
+    pub fn typical(&self) -> Duration {
+        match self {
+            Timing::Once(time) => *time,
+            Timing::Bench(stats) => stats.median,
+        }
+    }
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Timing::Once(time) => write!(f, "{time:.2?}"),
+            Timing::Bench(stats) => write!(f, "{stats}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: Part,
+    /// The answer, or why the part could not produce one.
+    pub outcome: Result<String, Error>,
+    pub timing: Option<Timing>,
+    /// What the first run allocated, with the `count-allocations` feature.
+    pub allocs: Option<AllocStats>,
+    /// Set once the answer has been checked against a known answer.
+    pub verdict: Option<Verdict>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub day: u8,
+    /// How long parsing took, or why it failed. Parts are only run if parsing succeeds.
+    pub parse: Result<Timing, Error>,
+    /// What parsing allocated, with the `count-allocations` feature.
+    pub parse_allocs: Option<AllocStats>,
+    pub parts: Vec<PartResult>,
+}
+
+impl DayResult {
+    /// True if any phase failed or gave an answer that contradicts a known answer.
+    pub fn failed(&self) -> bool {
+        self.parse.is_err()
+            || self.parts.iter().any(|part| {
+                part.outcome.is_err() || matches!(part.verdict, Some(Verdict::Incorrect { .. }))
+            })
+    }
+}
+
+/// Displays an answer or an error, so a run that fails counts as a different answer when benchmarking.
+struct Attempt<T>(Result<T, Error>);
+
+impl<T: Display> Display for Attempt<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Ok(answer) => write!(f, "{answer}"),
+            Err(err) => write!(f, "error: {err}"),
+        }
+    }
+}
+
+pub(crate) fn solve_part<T: Display>(
+    options: &RunOptions,
+    part: Part,
+    mut solve: impl FnMut() -> Result<T, Error>,
+) -> PartResult {
+    let watchdog = cancel::Watchdog::new(options.timeout);
+    let mut solve = || watchdog.run(|| isolate::catch(&mut solve));
+    let ((first, time), allocs) = allocs::measure(|| {
+        let timer = Instant::now();
+        let first = solve();
+        (first, timer.elapsed())
+    });
+    // Also covers solvers that never check their token, once they finish
+    let first = match options.timeout {
+        Some(limit) if time > limit || matches!(first, Err(Error::Cancelled)) => {
+            Err(Error::TimedOut(limit))
+        }
+        _ => first,
+    };
+    let result = match (first, &options.bench) {
+        (Err(err), _) => Err(err),
+        (Ok(answer), None) => Ok((answer.to_string(), Timing::Once(time))),
+        (Ok(_), Some(config)) => {
+            let mut cancelled = false;
+            let result = bench::bench_answer(config, || {
+                let attempt = solve();
+                cancelled |= matches!(attempt, Err(Error::Cancelled));
+                Attempt(attempt)
+            });
+            match (result, options.timeout) {
+                (_, Some(limit)) if cancelled => Err(Error::TimedOut(limit)),
+                (result, _) => result
+                    .map(|(answer, stats)| (answer, Timing::Bench(stats)))
+                    .map_err(Error::from),
+            }
+        }
+    };
+    match result {
+        Ok((answer, timing)) => PartResult {
+            part,
+            outcome: Ok(answer),
+            timing: Some(timing),
+            allocs,
+            verdict: None,
+        },
+        Err(err) => PartResult {
+            part,
+            outcome: Err(err),
+            timing: None,
+            allocs,
+            verdict: None,
+        },
+    }
+}
+
+/// Parses the input once, then runs and times the requested part, or both parts when `part` is `None`.
+///
+/// Every phase runs behind a panic boundary, so a panicking solver is reported as a failed phase. With
+/// a timeout, any run of a part that passes it, benchmarked runs included, is cancelled and the part
+/// reported as timed out.
+pub fn solve_puzzles<S: Solution>(input: &str, options: &RunOptions) -> DayResult {
+    let ((parsed, time), parse_allocs) = allocs::measure(|| {
+        let timer = Instant::now();
+        let parsed = isolate::catch(|| Ok(S::parse(input)?));
+        (parsed, timer.elapsed())
+    });
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return DayResult {
+                day: S::DAY,
+                parse: Err(err.locate(S::DAY, input)),
+                parse_allocs,
+                parts: Vec::new(),
+            }
+        }
+    };
+    let parse = match &options.bench {
+        None => Timing::Once(time),
+        Some(config) => Timing::Bench(bench::bench(config, || S::parse(input))),
+    };
+    let locate = |err: Error| err.locate(S::DAY, input);
+    let mut parts = Vec::new();
+    if options.part != Some(Part::Two) {
+        parts.push(solve_part(options, Part::One, || {
+            S::part1(&parsed).map_err(locate)
+        }));
+    }
+    if options.part != Some(Part::One) {
+        parts.push(solve_part(options, Part::Two, || {
+            S::part2(&parsed).map_err(locate)
+        }));
+    }
+    DayResult {
+        day: S::DAY,
+        parse: Ok(parse),
+        parse_allocs,
+        parts,
+    }
 }
@@ -0,0 +1,616 @@
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use aoc2023::answers::Answers;
+use aoc2023::bench::{self, BenchConfig};
+use aoc2023::client::{Client, Outcome};
+use aoc2023::days::{self, Day};
+use aoc2023::history::{self, Baseline, History};
+use aoc2023::input::{self, InputError, InputSource};
+use aoc2023::ledger::{Ledger, Submission};
+use aoc2023::report::{self, Format};
+use aoc2023::{
+    allocs, examples, gen, isolate, readme, scaffold, watch, DayResult, Part, RunOptions,
+};
+
+const README_PATH: &str = "README.md";
+const USAGE: &str = "\
+Usage: aoc <day | all | start..end> [options]
+       aoc readme [benchmark options]
+       aoc new <day>
+       aoc test <day | all | start..end>
+       aoc compare <day | all | start..end> [options]
+       aoc watch <day>
+       aoc gen <day> [--seed <n>] [--size <n>]
+       aoc fetch <day | all | start..end>
+       aoc submit <day> --part <1|2> [--answer <answer>]
+
+Commands:
+  readme                 Benchmark every day and rewrite the results table in README.md
+  new <day>              Create and register src/days/day<day>.rs and example/day<day>.txt
+  test                   Check every example in example/manifest.txt gives its expected answer
+  compare                Run every registered implementation of each part on the same input,
+                         check they agree and show their timings side by side
+  watch <day>            Re-run a day and its example tests whenever its input, examples or
+                         source change, showing what changed since the last run
+  gen <day>              Print a random input for a day, the same for the same seed and size
+  fetch                  Download puzzle inputs that aren't already in the input directory
+  submit <day>           Submit an answer, by default the one the solver gives for your input.
+                         Answers already submitted or outside known bounds are refused
+
+Options:
+  -p, --part <1|2>       Only run one part
+  -i, --input <file|->   Read input from a file, or stdin with '-'
+  -s, --sequential       Run one day at a time instead of in parallel, for cleaner timings.
+                         Benchmarks always run one day at a time
+  -b, --bench            Repeat every phase and report timing statistics
+      --runs <n>         Benchmark a fixed number of runs instead of a time budget
+      --budget <time>    Time to spend benchmarking each phase, e.g. 500ms or 2s (default 1s)
+      --warmup <n>       Untimed runs before benchmarking (default 3)
+      --compare <base>   Compare benchmarks with an earlier commit, or 'previous' for the last
+                         one benchmarked, and fail on slowdowns
+      --threshold <pct>  Slowdown that --compare fails on, in percent (default 10)
+  -t, --timeout <time>   Give up on a part after this long, e.g. 500ms or 10s, and report it as
+                         timed out. Solvers only stop early where they check for cancellation
+  -f, --format <fmt>     Output format: pretty, plain, json, csv or markdown (default pretty)
+  -a, --answer <answer>  The answer to submit instead of solving
+      --seed <n>         Seed for gen (default 0)
+      --size <n>         How big an input gen makes, e.g. lines or grid width (default 10)
+
+The session token for fetch and submit is read from `session` in aoc.toml or AOC_SESSION.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Run,
+    Readme,
+    New,
+    Test,
+    Compare,
+    Watch,
+    Gen,
+    Fetch,
+    Submit,
+    Help,
+}
+
+struct Args {
+    command: Command,
+    selection: String,
+    input: InputSource,
+    options: RunOptions,
+    format: Format,
+    answer: Option<String>,
+    compare: Option<Baseline>,
+    /// Fraction a phase may slow down by before `--compare` fails.
+    threshold: f64,
+    sequential: bool,
+    seed: u64,
+    size: usize,
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next().ok_or(format!("{flag} needs a value"))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut command = Command::Run;
+    let mut selection = None;
+    let mut input = InputSource::Default;
+    let mut options = RunOptions::default();
+    let mut bench = BenchConfig::default();
+    let mut benchmarking = false;
+    let mut format = Format::default();
+    let mut answer = None;
+    let mut compare = None;
+    let mut threshold = 0.1;
+    let mut sequential = false;
+    let mut seed = 0;
+    let mut size = gen::DEFAULT_SIZE;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let value = next_value(&mut args, &arg)?;
+                options.part =
+                    Some(Part::from_number(&value).ok_or(format!("invalid part '{value}'"))?);
+            }
+            "--input" | "-i" => input = InputSource::from_arg(&next_value(&mut args, &arg)?),
+            "--bench" | "-b" => benchmarking = true,
+            "--sequential" | "-s" => sequential = true,
+            "--runs" => {
+                let value = next_value(&mut args, &arg)?;
+                bench.runs = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid run count '{value}'"))?,
+                );
+                benchmarking = true;
+            }
+            "--budget" => {
+                let value = next_value(&mut args, &arg)?;
+                bench.budget =
+                    bench::parse_duration(&value).ok_or(format!("invalid duration '{value}'"))?;
+                benchmarking = true;
+            }
+            "--warmup" => {
+                let value = next_value(&mut args, &arg)?;
+                bench.warmup = value
+                    .parse()
+                    .map_err(|_| format!("invalid warmup count '{value}'"))?;
+                benchmarking = true;
+            }
+            "--compare" => {
+                compare = Some(Baseline::from_arg(&next_value(&mut args, &arg)?));
+                benchmarking = true;
+            }
+            "--threshold" => {
+                let value = next_value(&mut args, &arg)?;
+                threshold = value
+                    .trim_end_matches('%')
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid threshold '{value}'"))?
+                    / 100.0;
+            }
+            "--timeout" | "-t" => {
+                let value = next_value(&mut args, &arg)?;
+                options.timeout = Some(
+                    bench::parse_duration(&value).ok_or(format!("invalid duration '{value}'"))?,
+                );
+            }
+            "--format" | "-f" => format = next_value(&mut args, &arg)?.parse()?,
+            "--answer" | "-a" => {
+                // Trimmed here so the answer submitted, checked and recorded is the same
+                let value = next_value(&mut args, &arg)?.trim().to_string();
+                if value.is_empty() {
+                    return Err(format!("{arg} needs a non-empty answer"));
+                }
+                answer = Some(value);
+            }
+            "--seed" => {
+                let value = next_value(&mut args, &arg)?;
+                seed = value
+                    .parse()
+                    .map_err(|_| format!("invalid seed '{value}'"))?;
+            }
+            "--size" => {
+                let value = next_value(&mut args, &arg)?;
+                size = value
+                    .parse()
+                    .map_err(|_| format!("invalid size '{value}'"))?;
+            }
+            // Nothing after it matters, not even mistakes
+            "--help" | "-h" => {
+                command = Command::Help;
+                break;
+            }
+            "readme" if command == Command::Run && selection.is_none() => command = Command::Readme,
+            "new" if command == Command::Run && selection.is_none() => command = Command::New,
+            "test" if command == Command::Run && selection.is_none() => command = Command::Test,
+            "compare" if command == Command::Run && selection.is_none() => {
+                command = Command::Compare
+            }
+            "watch" if command == Command::Run && selection.is_none() => command = Command::Watch,
+            "gen" if command == Command::Run && selection.is_none() => command = Command::Gen,
+            "fetch" if command == Command::Run && selection.is_none() => command = Command::Fetch,
+            "submit" if command == Command::Run && selection.is_none() => command = Command::Submit,
+            _ if selection.is_none() => selection = Some(arg),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+    if benchmarking {
+        options.bench = Some(bench);
+    }
+    Ok(Args {
+        command,
+        selection: selection.unwrap_or_else(|| "all".to_string()),
+        input,
+        options,
+        format,
+        answer,
+        compare,
+        threshold,
+        sequential,
+        seed,
+        size,
+    })
+}
+
+fn run(args: &Args) -> ExitCode {
+    let selected = match days::select(&args.selection) {
+        Ok(selected) => selected,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.input != InputSource::Default && selected.len() > 1 {
+        eprintln!("--input can only be used when running a single day");
+        return ExitCode::FAILURE;
+    }
+    // Benchmarks and allocation counts are only meaningful with one day running at a time
+    let parallel = !args.sequential && args.options.bench.is_none() && !allocs::ENABLED;
+    let start = Instant::now();
+    let solve = |day: &Day| {
+        let input = input::load(day.number, &args.input)?;
+        Ok::<_, InputError>(day.solve(&input, &args.options))
+    };
+    let mut reporter = args.format.stdout_reporter();
+    let mut status = ExitCode::SUCCESS;
+    let mut results = Vec::new();
+    let reported = reporter.start().and_then(|_| {
+        days::run_in_order(&selected, parallel, solve, |solved| {
+            match solved {
+                Ok(mut result) => {
+                    // Known answers only apply to the default input
+                    if args.input == InputSource::Default {
+                        match Answers::load(result.day) {
+                            Ok(Some(answers)) => answers.verify(&mut result),
+                            Ok(None) => {}
+                            Err(err) => {
+                                eprintln!("{err}");
+                                status = ExitCode::FAILURE;
+                            }
+                        }
+                    }
+                    if result.failed() {
+                        status = ExitCode::FAILURE;
+                    }
+                    reporter.day(&result)?;
+                    results.push(result);
+                }
+                Err(err) => {
+                    eprintln!("{err}");
+                    status = ExitCode::FAILURE;
+                }
+            }
+            Ok::<_, io::Error>(())
+        })?;
+        reporter.finish()
+    });
+    if let Err(err) = reported {
+        eprintln!("could not write results: {err}");
+        return ExitCode::FAILURE;
+    }
+    // Goes to stderr so it doesn't get mixed into machine-readable formats
+    if selected.len() > 1 {
+        eprint!("\n{}", report::summary(&results));
+        let mode = if parallel {
+            "in parallel"
+        } else {
+            "sequentially"
+        };
+        eprintln!("Total time: {:.2?} ({mode})", start.elapsed());
+    }
+    if args.options.bench.is_some() && !record_history(&results, args) {
+        status = ExitCode::FAILURE;
+    }
+    status
+}
+
+/// Appends benchmark timings to the history and, with `--compare`, reports how they changed.
+/// Returns false if any phase slowed down by more than the threshold.
+fn record_history(results: &[DayResult], args: &Args) -> bool {
+    let mut history = match History::load() {
+        Ok(history) => history,
+        Err(err) => {
+            eprintln!("{err}");
+            return false;
+        }
+    };
+    let entries = history::entries(
+        results,
+        &history::current_commit(),
+        &history::machine_name(),
+    );
+    let mut ok = true;
+    if let Some(baseline) = &args.compare {
+        let changes = history.compare(&entries, baseline);
+        if changes.is_empty() {
+            eprintln!("\nNothing to compare with in {}", History::path().display());
+        } else {
+            eprintln!("\nCompared with earlier benchmarks:");
+        }
+        for change in changes {
+            if change.is_slowdown(args.threshold) {
+                eprintln!("  {change} slower");
+                ok = false;
+            } else {
+                eprintln!("  {change}");
+            }
+        }
+    }
+    if let Err(err) = history.append(entries) {
+        eprintln!("{err}");
+        ok = false;
+    }
+    ok
+}
+
+fn update_readme(args: &Args) -> ExitCode {
+    let options = RunOptions {
+        bench: Some(args.options.bench.unwrap_or_default()),
+        ..Default::default()
+    };
+    let mut results = Vec::new();
+    for day in days::DAYS {
+        match input::load(day.number, &InputSource::Default) {
+            Ok(input) => {
+                eprintln!("Benchmarking day {}", day.number);
+                let mut result = day.solve(&input, &options);
+                match Answers::load(day.number) {
+                    Ok(Some(answers)) => answers.verify(&mut result),
+                    Ok(None) => {}
+                    Err(err) => eprintln!("{err}"),
+                }
+                results.push(result);
+            }
+            Err(err) => eprintln!("Skipping timings: {err}"),
+        }
+    }
+    record_history(&results, args);
+    let updated = std::fs::read_to_string(README_PATH)
+        .map_err(|err| format!("could not read {README_PATH}: {err}"))
+        .and_then(|contents| readme::replace_table(&contents, &readme::render_table(&results)))
+        .and_then(|contents| {
+            std::fs::write(README_PATH, contents)
+                .map_err(|err| format!("could not write {README_PATH}: {err}"))
+        });
+    match updated {
+        Ok(()) => {
+            eprintln!("Updated {README_PATH}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn new_day(args: &Args) -> ExitCode {
+    let Ok(day) = args.selection.parse() else {
+        eprintln!("invalid day '{}'", args.selection);
+        return ExitCode::FAILURE;
+    };
+    match scaffold::create(Path::new("."), day) {
+        Ok(written) => {
+            for path in written {
+                eprintln!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn test_examples(args: &Args) -> ExitCode {
+    let selected = match days::select(&args.selection) {
+        Ok(selected) => selected,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let entries = match examples::load() {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut status = ExitCode::SUCCESS;
+    for day in selected {
+        let entries: Vec<_> = entries
+            .iter()
+            .filter(|entry| entry.day == day.number)
+            .filter(|entry| args.options.part.is_none_or(|part| entry.part == part))
+            .collect();
+        if entries.is_empty() {
+            println!("day {}: no examples", day.number);
+        }
+        for entry in entries {
+            let label = format!("day {} part {} {}", entry.day, entry.part, entry.file);
+            match examples::check(entry) {
+                Ok(()) => println!("{label}: {} ✅", entry.expected),
+                Err(err) => {
+                    println!("{label}: ❌ {err}");
+                    status = ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+    status
+}
+
+fn compare_variants(args: &Args) -> ExitCode {
+    let selected = match days::select(&args.selection) {
+        Ok(selected) => selected,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.input != InputSource::Default && selected.len() > 1 {
+        eprintln!("--input can only be used when comparing a single day");
+        return ExitCode::FAILURE;
+    }
+    let mut status = ExitCode::SUCCESS;
+    for day in selected {
+        let compared = input::load(day.number, &args.input)
+            .map_err(|err| err.to_string())
+            .and_then(|input| {
+                day.compare(&input, &args.options)
+                    .map_err(|err| match err.snippet() {
+                        Some(snippet) => format!("day {}: {err}\n{snippet}", day.number),
+                        None => format!("day {}: {err}", day.number),
+                    })
+            });
+        match compared {
+            Ok(comparisons) => {
+                for comparison in comparisons {
+                    print!("{comparison}");
+                    if !comparison.agrees() {
+                        status = ExitCode::FAILURE;
+                    }
+                }
+            }
+            Err(message) => {
+                eprintln!("{message}");
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
+
+fn generate(args: &Args) -> ExitCode {
+    let day = match args.selection.parse().ok().and_then(days::get) {
+        Some(day) => day,
+        None => {
+            eprintln!("day {} is not implemented", args.selection);
+            return ExitCode::FAILURE;
+        }
+    };
+    match day.generate(args.seed, args.size) {
+        Some(input) => {
+            print!("{input}");
+            ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!("day {} has no input generator", day.number);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(args: &Args) -> ExitCode {
+    let selected = match days::select(&args.selection) {
+        Ok(selected) => selected,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut status = ExitCode::SUCCESS;
+    for day in selected {
+        let path = input::default_path(day.number);
+        let cached = path.exists();
+        match client.cached_input(day.number, &path) {
+            Ok(_) if cached => eprintln!("Day {}: already have {}", day.number, path.display()),
+            Ok(_) => eprintln!("Day {}: saved {}", day.number, path.display()),
+            Err(err) => {
+                eprintln!("Day {}: {err}", day.number);
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
+
+fn submit(args: &Args) -> ExitCode {
+    let (Ok(number), Some(part)) = (args.selection.parse(), args.options.part) else {
+        eprintln!("submit needs a single day and --part");
+        return ExitCode::FAILURE;
+    };
+    let answer = match &args.answer {
+        Some(answer) => answer.clone(),
+        None => {
+            let Some(day) = days::get(number) else {
+                eprintln!("day {number} is not implemented");
+                return ExitCode::FAILURE;
+            };
+            let input = match input::load(number, &args.input) {
+                Ok(input) => input,
+                Err(err) => {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let result = day.solve(&input, &args.options);
+            match result.parse.and_then(|_| result.parts[0].outcome.clone()) {
+                Ok(answer) => answer,
+                Err(err) => {
+                    eprintln!("day {number} part {part} failed: {err}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    };
+    let mut ledger = match Ledger::load() {
+        Ok(ledger) => ledger,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(blocked) = ledger.check(number, part, &answer) {
+        eprintln!("Not submitting {answer} for day {number} part {part}: {blocked}");
+        return ExitCode::FAILURE;
+    }
+    let outcome = Client::from_env().and_then(|client| client.submit(number, part, &answer));
+    match outcome {
+        Ok(outcome) => {
+            eprintln!("Day {number} part {part}: {answer} is {outcome}");
+            let submission = Submission {
+                day: number,
+                part,
+                answer,
+                outcome: outcome.clone(),
+            };
+            if let Err(err) = ledger.record(submission) {
+                eprintln!("{err}");
+            }
+            match outcome {
+                Outcome::Correct => ExitCode::SUCCESS,
+                _ => ExitCode::FAILURE,
+            }
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    isolate::install_hook();
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match args.command {
+        Command::Run => run(&args),
+        Command::Readme => update_readme(&args),
+        Command::New => new_day(&args),
+        Command::Test => test_examples(&args),
+        Command::Compare => compare_variants(&args),
+        Command::Watch => match args.selection.parse() {
+            Ok(day) if days::get(day).is_some() => watch::watch(day),
+            _ => {
+                eprintln!("day {} is not implemented", args.selection);
+                ExitCode::FAILURE
+            }
+        },
+        Command::Gen => generate(&args),
+        Command::Help => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Command::Fetch => fetch(&args),
+        Command::Submit => submit(&args),
+    }
+}
@@ -0,0 +1,73 @@
+//! The example manifest format. Also compiled into `build.rs` to generate a test per entry, so it
+//! can't depend on the rest of the crate other than [`Part`], which `build.rs` compiles too.
+
+use std::collections::HashMap;
+
+use crate::part::Part;
+
+/// Where the example manifest lives, relative to the crate root.
+pub const MANIFEST_PATH: &str = "example/manifest.txt";
+
+/// One example: a file in `example/`, the part it applies to and the answer it should give.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: Part,
+    pub file: String,
+    pub expected: String,
+}
+
+impl Entry {
+    /// A name for the entry that is also a valid identifier, e.g. `day8_part1_day8_2`.
+    pub fn name(&self) -> String {
+        let stem = self.file.strip_suffix(".txt").unwrap_or(&self.file);
+        let stem: String = stem
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("day{}_part{}_{stem}", self.day, self.part)
+    }
+}
+
+/// Parses manifest lines of the form `<day> <part> <file> <expected>`, ignoring `#` comments.
+pub fn parse(input: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    // Each entry becomes a test function named after it, so no two names may be the same
+    let mut names = HashMap::new();
+    for (number, line) in input.lines().enumerate() {
+        let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<_> = line.split_whitespace().collect();
+        let entry = match fields[..] {
+            [day, part, file, expected] => {
+                day.parse()
+                    .ok()
+                    .zip(Part::from_number(part))
+                    .map(|(day, part)| Entry {
+                        day,
+                        part,
+                        file: file.to_string(),
+                        expected: expected.to_string(),
+                    })
+            }
+            _ => None,
+        };
+        let entry = entry.ok_or(format!(
+            "line {}: expected `<day> <1|2> <file> <answer>`, found '{line}'",
+            number + 1
+        ))?;
+        if let Some(first) = names.insert(entry.name(), number + 1) {
+            return Err(format!(
+                "line {}: day {} part {} with '{}' is already listed on line {first}",
+                number + 1,
+                entry.day,
+                entry.part,
+                entry.file
+            ));
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
@@ -0,0 +1,223 @@
+use std::fmt::Debug;
+
+/// The primitive integer types, for the functions in this module.
+pub trait Integer: Copy + Ord + Debug + TryFrom<i128> + TryInto<i128> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_div(self, other: Self) -> Option<Self>;
+    fn checked_rem(self, other: Self) -> Option<Self>;
+
+    fn checked_abs(self) -> Option<Self> {
+        match self < Self::ZERO {
+            true => Self::ZERO.checked_sub(self),
+            false => Some(self),
+        }
+    }
+}
+
+/// The signed integer types, for results that can be negative.
+pub trait Signed: Integer {}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+            fn checked_div(self, other: Self) -> Option<Self> {
+                <$t>::checked_div(self, other)
+            }
+            fn checked_rem(self, other: Self) -> Option<Self> {
+                <$t>::checked_rem(self, other)
+            }
+        })*
+    };
+}
+
+integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+/// The greatest common divisor of `a` and `b`, never negative. `gcd(0, 0)` is 0. `None` only if
+/// the result doesn't fit, as for `gcd(i64::MIN, 0)`.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> Option<T> {
+    while b != T::ZERO {
+        // Only fails for MIN % -1, which is 0
+        let remainder = a.checked_rem(b).unwrap_or(T::ZERO);
+        a = b;
+        b = remainder;
+    }
+    a.checked_abs()
+}
+
+/// The least common multiple of `a` and `b`, never negative, or `None` if it overflows. It's 0
+/// if either is.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    // Dividing first keeps the intermediate result no bigger than the answer
+    a.checked_div(gcd(a, b)?)?.checked_mul(b)?.checked_abs()
+}
+
+/// The least common multiple of all of `values`, 1 if there are none, or `None` if it overflows.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, |acc, value| lcm(acc, value))
+}
+
+/// The extended Euclidean algorithm: `(g, x, y)` where `g = gcd(a, b)` and `a * x + b * y = g`,
+/// or `None` if a step overflows.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> Option<(T, T, T)> {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let quotient = old_r.checked_div(r)?;
+        (old_r, r) = (r, old_r.checked_sub(quotient.checked_mul(r)?)?);
+        (old_x, x) = (x, old_x.checked_sub(quotient.checked_mul(x)?)?);
+        (old_y, y) = (y, old_y.checked_sub(quotient.checked_mul(y)?)?);
+    }
+    if old_r < T::ZERO {
+        let negate = |value: T| T::ZERO.checked_sub(value);
+        return Some((negate(old_r)?, negate(old_x)?, negate(old_y)?));
+    }
+    Some((old_r, old_x, old_y))
+}
+
+/// `a * b` modulo `modulus`, or `None` if it overflows.
+fn mul_mod(a: i128, b: i128, modulus: i128) -> Option<i128> {
+    a.rem_euclid(modulus)
+        .checked_mul(b.rem_euclid(modulus))
+        .map(|product| product % modulus)
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair with the
+/// Chinese Remainder Theorem. The moduli don't need to be coprime.
+///
+/// Returns the smallest non-negative `x` and the lcm of the moduli, which every solution is `x`
+/// plus a multiple of. `None` if the congruences contradict each other, a modulus isn't positive
+/// or the numbers overflow. An empty system is `(0, 1)`.
+pub fn crt<T: Integer>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let (mut x, mut modulus) = (0i128, 1i128);
+    for (residue, next) in congruences {
+        let residue: i128 = residue.try_into().ok()?;
+        let next: i128 = next.try_into().ok()?;
+        if next <= 0 {
+            return None;
+        }
+        let residue = residue.rem_euclid(next);
+        // modulus * p + next * q = g, so x + modulus * k solves both when k = (residue - x) / g * p
+        let (g, p, _) = extended_gcd(modulus, next)?;
+        let difference = residue - x;
+        if difference % g != 0 {
+            return None;
+        }
+        let step = next / g;
+        let k = mul_mod(difference / g, p, step)?;
+        let combined = modulus.checked_mul(step)?;
+        x = x.checked_add(modulus.checked_mul(k)?)?.rem_euclid(combined);
+        modulus = combined;
+    }
+    Some((T::try_from(x).ok()?, T::try_from(modulus).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_test() {
+        assert_eq!(gcd(12u32, 18), Some(6));
+        assert_eq!(gcd(-12i64, 18), Some(6));
+        assert_eq!(gcd(12i64, -18), Some(6));
+        assert_eq!(gcd(0u8, 7), Some(7));
+        assert_eq!(gcd(0u8, 0), Some(0));
+        assert_eq!(gcd(i64::MIN, 2), Some(2));
+        assert_eq!(gcd(i64::MIN, -1), Some(1));
+        assert_eq!(gcd(i64::MIN, 0), None);
+    }
+
+    #[test]
+    fn lcm_test() {
+        assert_eq!(lcm(4usize, 6), Some(12));
+        assert_eq!(lcm(-4i32, 6), Some(12));
+        assert_eq!(lcm(0u64, 6), Some(0));
+        // a * b alone would overflow, but the lcm fits
+        assert_eq!(lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(lcm(u64::MAX, 2), None);
+        assert_eq!(lcm(200u8, 3), None);
+        assert_eq!(lcm_all([2u32, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u32>::new()), Some(1));
+        assert_eq!(lcm_all([1u8 << 7, 3]), None);
+    }
+
+    #[test]
+    fn extended_gcd_test() {
+        for (a, b) in [
+            (240i64, 46),
+            (-240, 46),
+            (240, -46),
+            (0, 5),
+            (5, 0),
+            (17, 17),
+        ] {
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+            assert_eq!(Some(g), gcd(a, b), "gcd of {a} and {b}");
+            assert_eq!(a * x + b * y, g, "{a} * {x} + {b} * {y}");
+        }
+        assert_eq!(extended_gcd(0i32, 0), Some((0, 1, 0)));
+    }
+
+    #[test]
+    fn crt_test() {
+        assert_eq!(crt([(2u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Not coprime, but consistent
+        assert_eq!(crt([(2i64, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1i32, 5)]), Some((4, 5)));
+        assert_eq!(crt([(1u32, 0)]), None);
+        assert_eq!(crt(Vec::<(u8, u8)>::new()), Some((0, 1)));
+        // Fits in the intermediate i128s but not the result
+        assert_eq!(crt([(1u8, 251), (1, 241)]), None);
+    }
+
+    /// Checks `crt` against trying every candidate, for small systems.
+    #[test]
+    fn crt_brute_force_test() {
+        for m1 in 1..12i64 {
+            for m2 in 1..12 {
+                for a1 in 0..m1 {
+                    for a2 in 0..m2 {
+                        let modulus = lcm(m1, m2).unwrap();
+                        let expected = (0..modulus).find(|x| x % m1 == a1 && x % m2 == a2);
+                        assert_eq!(
+                            crt([(a1, m1), (a2, m2)]),
+                            expected.map(|x| (x, modulus)),
+                            "x = {a1} mod {m1}, x = {a2} mod {m2}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Helpers for the shapes puzzle inputs keep coming in. They return slices of their input, and
+//! errors pointing into it, so [`ParseError::locate`] can find the line and column later.
+
+use std::any::type_name;
+use std::iter;
+use std::str::FromStr;
+
+use crate::error::parse_number;
+use crate::math::Integer;
+use crate::ParseError;
+
+/// The whitespace-separated numbers in `span`, failing on anything that isn't one.
+pub fn numbers<'a, T: FromStr + 'a>(
+    span: &'a str,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+    span.split_whitespace().map(parse_number)
+}
+
+/// Every integer in `line`, skipping whatever is between them. A `-` right before the digits
+/// makes the number negative when `T` is signed, and is skipped like any other character when
+/// it isn't.
+pub fn integers<'a, T: Integer + FromStr + 'a>(
+    line: &'a str,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+    let signed = T::try_from(-1).is_ok();
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+        let start = match signed && i > 0 && bytes[i - 1] == b'-' {
+            true => i - 1,
+            false => i,
+        };
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        // Only ASCII has been skipped, so these are character boundaries
+        let span = &line[start..i];
+        Some(span.parse().map_err(|_| {
+            ParseError::new(span, format!("a number that fits in {}", type_name::<T>()))
+        }))
+    })
+}
+
+/// The sections of `input` between blank lines, without their surrounding whitespace. Runs of
+/// blank lines count as one, and blank lines at the start or end are ignored.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    let mut lines = input.split_inclusive('\n').peekable();
+    let offset = |line: &str| line.as_ptr() as usize - input.as_ptr() as usize;
+    iter::from_fn(move || {
+        let first = lines.find(|line| !line.trim().is_empty())?;
+        let mut end = offset(first) + first.len();
+        while let Some(line) = lines.next_if(|line| !line.trim().is_empty()) {
+            end = offset(line) + line.len();
+        }
+        Some(input[offset(first)..end].trim_end())
+    })
+}
+
+/// Splits `line` into the key before the first `:` and the value after it, both trimmed.
+pub fn key_value(line: &str) -> Result<(&str, &str), ParseError> {
+    let (key, value) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::after(line, "':'"))?;
+    match key.trim() {
+        "" => Err(ParseError::new(key, "a key before ':'")),
+        key => Ok((key, value.trim())),
+    }
+}
+
+/// The value of a `key: value` line, which must have the key `key`.
+pub fn value<'a>(line: &'a str, key: &str) -> Result<&'a str, ParseError> {
+    match key_value(line)? {
+        (found, value) if found == key => Ok(value),
+        (found, _) => Err(ParseError::new(found, format!("'{key}'"))),
+    }
+}
+
+/// Splits a record like `Game 12: ...` into its number and the payload after the `:`, checking the
+/// record starts with `label`.
+pub fn labelled<'a, T: FromStr>(line: &'a str, label: &str) -> Result<(T, &'a str), ParseError> {
+    let (key, payload) = key_value(line)?;
+    let mut words = key.split_whitespace();
+    match (words.next(), words.next(), words.next()) {
+        (Some(found), Some(id), None) if found == label => Ok((parse_number(id)?, payload)),
+        (Some(found), _, _) if found != label => Err(ParseError::new(found, format!("'{label}'"))),
+        _ => Err(ParseError::new(key, format!("'{label} <number>'"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_test() {
+        let numbers: Result<Vec<u32>, _> = numbers(" 41 48  83 ").collect();
+        assert_eq!(numbers, Ok(vec![41, 48, 83]));
+        let input = "10 -3 x";
+        let err = super::numbers::<i64>(input)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err()
+            .locate(input);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 7: expected a number, found 'x'"
+        );
+    }
+
+    #[test]
+    fn integers_test() {
+        let line = "x=-12, y=5..-7";
+        let signed: Result<Vec<i32>, _> = integers(line).collect();
+        assert_eq!(signed, Ok(vec![-12, 5, -7]));
+        let unsigned: Result<Vec<u32>, _> = integers(line).collect();
+        assert_eq!(unsigned, Ok(vec![12, 5, 7]));
+        assert_eq!(integers::<u8>("").count(), 0);
+
+        let input = "1 300 2";
+        let err = integers::<u8>(input)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err()
+            .locate(input);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: expected a number that fits in u8, found '300'"
+        );
+    }
+
+    #[test]
+    fn sections_test() {
+        let input = "\nseeds: 1 2\n\n\na\nb\n  \r\nc\r\n\n";
+        let sections: Vec<_> = sections(input).collect();
+        assert_eq!(sections, ["seeds: 1 2", "a\nb", "c"]);
+        assert_eq!(super::sections("").count(), 0);
+        assert_eq!(super::sections("\n \n").count(), 0);
+    }
+
+    #[test]
+    fn key_value_test() {
+        assert_eq!(key_value("Time:  7  15"), Ok(("Time", "7  15")));
+        assert_eq!(value("seeds: 79 14", "seeds"), Ok("79 14"));
+        let input = "Distance 9";
+        let err = key_value(input).unwrap_err().locate(input);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 11: expected ':', found end of line"
+        );
+        let input = "Time: 7";
+        let err = value(input, "Distance").unwrap_err().locate(input);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected 'Distance', found 'Time'"
+        );
+        assert!(key_value(" : 7").is_err());
+    }
+
+    #[test]
+    fn labelled_test() {
+        assert_eq!(labelled("Card  12: 1 | 2", "Card"), Ok((12u32, "1 | 2")));
+        let input = "Game 1: 3 blue\nGame x: 1 red";
+        let line = input.lines().nth(1).unwrap();
+        let err = labelled::<u32>(line, "Game").unwrap_err().locate(input);
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 6: expected a number, found 'x'"
+        );
+        let err = labelled::<u32>("Card 1: 2", "Game").unwrap_err();
+        assert_eq!(
+            (err.expected.as_str(), err.found.as_str()),
+            ("'Game'", "'Card'")
+        );
+        let err = labelled::<u32>("Game: 2", "Game").unwrap_err();
+        assert_eq!(err.expected, "'Game <number>'");
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt::Write;
+
+use crate::answers::Verdict;
+use crate::{days, DayResult, Part};
+
+pub const START_MARKER: &str = "<!-- results:start -->";
+pub const END_MARKER: &str = "<!-- results:end -->";
+const LAST_DAY: u8 = 25;
+
+/// ✅ if every part matched a known answer, ❌ if any failed or mismatched, ❔ otherwise.
+fn verified(result: &DayResult) -> &'static str {
+    if result.failed() {
+        "❌"
+    } else if result
+        .parts
+        .iter()
+        .all(|part| part.verdict == Some(Verdict::Correct))
+    {
+        "✅"
+    } else {
+        "❔"
+    }
+}
+
+/// Renders the results table for every puzzle day, filling in the days present in `results`.
+pub fn render_table(results: &[DayResult]) -> String {
+    let mut table = String::from("|Day|Done|Verified|Part 1|Part 2|\n|---|---|---|---|---|\n");
+    for day in 1..=LAST_DAY {
+        let done = if days::get(day).is_some() {
+            "✅"
+        } else {
+            "❌"
+        };
+        let result = results.iter().find(|result| result.day == day);
+        let verified = result.map(verified).unwrap_or_default();
+        let time = |part: Part| {
+            result
+                .and_then(|result| result.parts.iter().find(|result| result.part == part))
+                .and_then(|result| result.timing)
+                .map(|timing| format!("{:.2?}", timing.typical()))
+                .unwrap_or_default()
+        };
+        writeln!(
+            table,
+            "|{day}|{done}|{verified}|{}|{}|",
+            time(Part::One),
+            time(Part::Two)
+        )
+        .unwrap();
+    }
+    table
+}
+
+/// Replaces everything between the result markers in `readme` with `table`.
+pub fn replace_table(readme: &str, table: &str) -> Result<String, String> {
+    let start = readme
+        .find(START_MARKER)
+        .ok_or(format!("missing {START_MARKER} marker"))?
+        + START_MARKER.len();
+    let end = readme[start..]
+        .find(END_MARKER)
+        .ok_or(format!("missing {END_MARKER} marker after {START_MARKER}"))?
+        + start;
+    Ok(format!("{}\n{table}{}", &readme[..start], &readme[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PartResult, Timing};
+    use std::time::Duration;
+
+    #[test]
+    fn render_table_test() {
+        let results = [DayResult {
+            day: 2,
+            parse: Ok(Timing::Once(Duration::from_micros(3))),
+            parse_allocs: None,
+            parts: vec![PartResult {
+                part: Part::One,
+                outcome: Ok("8".to_string()),
+                timing: Some(Timing::Once(Duration::from_micros(65))),
+                allocs: None,
+                verdict: None,
+            }],
+        }];
+        let table = render_table(&results);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 2 + LAST_DAY as usize);
+        assert_eq!(lines[2], "|1|✅||||");
+        assert_eq!(lines[3], "|2|✅|❔|65.00µs||");
+        assert_eq!(lines[26], "|25|❌||||");
+    }
+
+    #[test]
+    fn verified_test() {
+        let mut result = DayResult {
+            day: 7,
+            parse: Ok(Timing::Once(Duration::from_micros(3))),
+            parse_allocs: None,
+            parts: vec![PartResult {
+                part: Part::Two,
+                outcome: Ok("5905".to_string()),
+                timing: Some(Timing::Once(Duration::from_micros(65))),
+                allocs: None,
+                verdict: None,
+            }],
+        };
+        assert_eq!(verified(&result), "❔");
+        result.parts[0].verdict = Some(Verdict::Correct);
+        assert_eq!(verified(&result), "✅");
+        result.parts[0].verdict = Some(Verdict::Incorrect {
+            expected: "5906".to_string(),
+        });
+        assert_eq!(verified(&result), "❌");
+    }
+
+    #[test]
+    fn replace_table_test() {
+        let readme = format!("# Title\n{START_MARKER}\nold\n{END_MARKER}\nfooter\n");
+        assert_eq!(
+            replace_table(&readme, "new\n").unwrap(),
+            format!("# Title\n{START_MARKER}\nnew\n{END_MARKER}\nfooter\n")
+        );
+        assert!(replace_table("# Title\n", "new\n").is_err());
+    }
+}
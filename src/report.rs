@@ -0,0 +1,611 @@
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::allocs::AllocStats;
+use crate::answers::Verdict;
+use crate::{DayResult, Error, ParseError, Part, PartResult, Timing};
+
+const ANSI_YELLOW_BOLD: &str = "\x1B[1;33m";
+const ANSI_GREEN_BOLD: &str = "\x1B[1;32m";
+const ANSI_WHITE_BOLD: &str = "\x1B[1;37m";
+const ANSI_RED_BOLD: &str = "\x1B[1;31m";
+const ANSI_RESET: &str = "\x1b[0m";
+const CHRISTMAS_CHEER: &str = "🎄⭐🎅🎄⭐🎅🎄⭐🎅🎄⭐🎅";
+
+/// Receives each day's results as they are produced.
+pub trait Reporter {
+    fn start(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+    fn day(&mut self, result: &DayResult) -> io::Result<()>;
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Pretty,
+    Plain,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "pretty" => Ok(Format::Pretty),
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!("unknown format '{format}'")),
+        }
+    }
+}
+
+impl Format {
+    /// A reporter writing to stdout. Pretty output only uses colour on a terminal.
+    pub fn stdout_reporter(self) -> Box<dyn Reporter> {
+        let colour = io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        self.reporter(io::stdout(), colour)
+    }
+
+    pub fn reporter<W: Write + 'static>(self, out: W, colour: bool) -> Box<dyn Reporter> {
+        match self {
+            Format::Pretty => Box::new(Pretty::new(out, colour)),
+            Format::Plain => Box::new(Plain(out)),
+            Format::Json => Box::new(JsonLines(out)),
+            Format::Csv => Box::new(Csv(out)),
+            Format::Markdown => Box::new(Markdown(out)),
+        }
+    }
+}
+
+/// One line of tabular output: the parse phase or one part of a day.
+struct Row<'a> {
+    day: u8,
+    phase: String,
+    answer: Option<&'a str>,
+    error: Option<&'a Error>,
+    timing: Option<&'a Timing>,
+    allocs: Option<&'a AllocStats>,
+    verdict: Option<&'a Verdict>,
+}
+
+fn rows(result: &DayResult) -> Vec<Row<'_>> {
+    let mut rows = vec![Row {
+        day: result.day,
+        phase: "parse".to_string(),
+        answer: None,
+        error: result.parse.as_ref().err(),
+        timing: result.parse.as_ref().ok(),
+        allocs: result.parse_allocs.as_ref(),
+        verdict: None,
+    }];
+    rows.extend(result.parts.iter().map(|part| Row {
+        day: result.day,
+        phase: part.part.to_string(),
+        answer: part.outcome.as_deref().ok(),
+        error: part.outcome.as_ref().err(),
+        timing: part.timing.as_ref(),
+        allocs: part.allocs.as_ref(),
+        verdict: part.verdict.as_ref(),
+    }));
+    rows
+}
+
+fn nanos(duration: Duration) -> u128 {
+    duration.as_nanos()
+}
+
+/// The original terminal output: bold colours and festive borders.
+pub struct Pretty<W> {
+    out: W,
+    yellow: &'static str,
+    green: &'static str,
+    white: &'static str,
+    red: &'static str,
+    reset: &'static str,
+}
+
+impl<W: Write> Pretty<W> {
+    pub fn new(out: W, colour: bool) -> Self {
+        let pick = |code: &'static str| if colour { code } else { "" };
+        Pretty {
+            out,
+            yellow: pick(ANSI_YELLOW_BOLD),
+            green: pick(ANSI_GREEN_BOLD),
+            white: pick(ANSI_WHITE_BOLD),
+            red: pick(ANSI_RED_BOLD),
+            reset: pick(ANSI_RESET),
+        }
+    }
+
+    fn part(&mut self, part: &PartResult) -> io::Result<()> {
+        let Pretty {
+            out,
+            yellow,
+            green,
+            white,
+            red,
+            reset,
+        } = self;
+        let label = part.part;
+        match (&part.outcome, &part.verdict) {
+            (Ok(answer), None) => {
+                writeln!(out, "{white}Part {label} Answer: \n{green}{answer}{reset}")?;
+            }
+            (Ok(answer), Some(Verdict::Correct)) => {
+                writeln!(
+                    out,
+                    "{white}Part {label} Answer: \n{green}{answer}{reset} ✅"
+                )?;
+            }
+            (Ok(answer), Some(verdict)) => {
+                writeln!(
+                    out,
+                    "{white}Part {label} Answer: \n{red}{answer}{reset} {verdict}"
+                )?;
+            }
+            (Err(err), _) => {
+                writeln!(out, "{white}Part {label} Answer: \n{red}{err}{reset}")?;
+                if let Some(snippet) = err.snippet() {
+                    writeln!(out, "{snippet}")?;
+                }
+            }
+        }
+        if let Some(timing) = &part.timing {
+            writeln!(out, "Time taken: \n{yellow}{timing}{reset}")?;
+        }
+        if let Some(allocs) = &part.allocs {
+            writeln!(out, "Allocated: \n{yellow}{allocs}{reset}")?;
+        }
+        writeln!(out)
+    }
+}
+
+impl<W: Write> Reporter for Pretty<W> {
+    fn day(&mut self, result: &DayResult) -> io::Result<()> {
+        let (white, yellow, red, reset) = (self.white, self.yellow, self.red, self.reset);
+        writeln!(self.out, "Day {}", result.day)?;
+        writeln!(self.out, "\n{CHRISTMAS_CHEER}")?;
+        match &result.parse {
+            Ok(timing) => {
+                writeln!(
+                    self.out,
+                    "{white}Parse time taken: \n{yellow}{timing}{reset}"
+                )?;
+                if let Some(allocs) = &result.parse_allocs {
+                    writeln!(
+                        self.out,
+                        "{white}Parse allocated: \n{yellow}{allocs}{reset}"
+                    )?;
+                }
+                writeln!(self.out)?;
+            }
+            Err(err) => {
+                writeln!(self.out, "{white}Parse failed: \n{red}{err}{reset}")?;
+                if let Some(snippet) = err.snippet() {
+                    writeln!(self.out, "{snippet}")?;
+                }
+                writeln!(self.out)?;
+            }
+        }
+        for part in &result.parts {
+            self.part(part)?;
+        }
+        writeln!(self.out, "{CHRISTMAS_CHEER}\n")
+    }
+}
+
+/// One line per phase with no colour or decoration.
+pub struct Plain<W>(pub W);
+
+impl<W: Write> Reporter for Plain<W> {
+    fn day(&mut self, result: &DayResult) -> io::Result<()> {
+        for row in rows(result) {
+            let mut line = format!("day {} ", row.day);
+            match row.phase.as_str() {
+                "parse" => line.push_str("parse"),
+                part => write!(line, "part {part}").unwrap(),
+            }
+            if let Some(answer) = row.answer {
+                write!(line, ": {answer}").unwrap();
+            }
+            if let Some(verdict) = row.verdict {
+                write!(line, " {verdict}").unwrap();
+            }
+            if let Some(error) = row.error {
+                write!(line, ": error: {error}").unwrap();
+            }
+            if let Some(timing) = row.timing {
+                write!(line, " ({timing})").unwrap();
+            }
+            if let Some(allocs) = row.allocs {
+                write!(line, " [{allocs}]").unwrap();
+            }
+            writeln!(self.0, "{line}")?;
+            if let Some(snippet) = row.error.and_then(Error::snippet) {
+                writeln!(self.0, "{snippet}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// One JSON object per phase, one per line.
+pub struct JsonLines<W>(pub W);
+
+impl<W: Write> Reporter for JsonLines<W> {
+    fn day(&mut self, result: &DayResult) -> io::Result<()> {
+        for row in rows(result) {
+            let mut line = format!(
+                "{{\"day\":{},\"phase\":{}",
+                row.day,
+                json_string(&row.phase)
+            );
+            if let Some(answer) = row.answer {
+                write!(line, ",\"answer\":{}", json_string(answer)).unwrap();
+            }
+            match row.verdict {
+                Some(Verdict::Correct) => line.push_str(",\"verified\":true"),
+                Some(Verdict::Incorrect { expected }) => write!(
+                    line,
+                    ",\"verified\":false,\"expected\":{}",
+                    json_string(expected)
+                )
+                .unwrap(),
+                None => {}
+            }
+            if let Some(error) = row.error {
+                write!(line, ",\"error\":{}", json_string(&error.to_string())).unwrap();
+            }
+            if let Some(Error::Parse(ParseError {
+                position: Some(position),
+                ..
+            })) = row.error
+            {
+                write!(
+                    line,
+                    ",\"line\":{},\"column\":{}",
+                    position.line, position.column
+                )
+                .unwrap();
+            }
+            match row.timing {
+                Some(Timing::Once(time)) => write!(line, ",\"time_ns\":{}", nanos(*time)).unwrap(),
+                Some(Timing::Bench(stats)) => write!(
+                    line,
+                    ",\"time_ns\":{},\"runs\":{},\"min_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"stddev_ns\":{},\"p95_ns\":{}",
+                    nanos(stats.median),
+                    stats.runs,
+                    nanos(stats.min),
+                    nanos(stats.median),
+                    nanos(stats.mean),
+                    nanos(stats.stddev),
+                    nanos(stats.p95)
+                )
+                .unwrap(),
+                None => {}
+            }
+            if let Some(allocs) = row.allocs {
+                write!(
+                    line,
+                    ",\"allocations\":{},\"allocated_bytes\":{},\"peak_bytes\":{}",
+                    allocs.count, allocs.bytes, allocs.peak
+                )
+                .unwrap();
+            }
+            line.push('}');
+            writeln!(self.0, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Comma separated values with a header row. Durations are in nanoseconds.
+pub struct Csv<W>(pub W);
+
+impl<W: Write> Reporter for Csv<W> {
+    fn start(&mut self) -> io::Result<()> {
+        writeln!(
+            self.0,
+            "day,phase,answer,verified,expected,error,time_ns,runs,min_ns,median_ns,mean_ns,stddev_ns,p95_ns,allocations,allocated_bytes,peak_bytes"
+        )
+    }
+
+    fn day(&mut self, result: &DayResult) -> io::Result<()> {
+        for row in rows(result) {
+            let timing = match row.timing {
+                Some(Timing::Once(time)) => format!("{},1,,,,,", nanos(*time)),
+                Some(Timing::Bench(stats)) => format!(
+                    "{},{},{},{},{},{},{}",
+                    nanos(stats.median),
+                    stats.runs,
+                    nanos(stats.min),
+                    nanos(stats.median),
+                    nanos(stats.mean),
+                    nanos(stats.stddev),
+                    nanos(stats.p95)
+                ),
+                None => ",,,,,,".to_string(),
+            };
+            let verdict = match row.verdict {
+                Some(Verdict::Correct) => "true,".to_string(),
+                Some(Verdict::Incorrect { expected }) => format!("false,{}", csv_field(expected)),
+                None => ",".to_string(),
+            };
+            let allocs = match row.allocs {
+                Some(allocs) => format!("{},{},{}", allocs.count, allocs.bytes, allocs.peak),
+                None => ",,".to_string(),
+            };
+            writeln!(
+                self.0,
+                "{},{},{},{verdict},{},{timing},{allocs}",
+                row.day,
+                row.phase,
+                csv_field(row.answer.unwrap_or_default()),
+                csv_field(&row.error.map(Error::to_string).unwrap_or_default()),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+/// A Markdown table with one row per phase.
+pub struct Markdown<W>(pub W);
+
+impl<W: Write> Reporter for Markdown<W> {
+    fn start(&mut self) -> io::Result<()> {
+        writeln!(self.0, "|Day|Phase|Answer|Time|")?;
+        writeln!(self.0, "|---|---|---|---|")
+    }
+
+    fn day(&mut self, result: &DayResult) -> io::Result<()> {
+        for row in rows(result) {
+            let phase = match row.phase.as_str() {
+                "parse" => "Parse".to_string(),
+                part => format!("Part {part}"),
+            };
+            let answer = match (row.answer, row.error) {
+                (Some(answer), _) => match row.verdict {
+                    Some(verdict) => format!("{} {}", markdown_cell(answer), verdict),
+                    None => markdown_cell(answer),
+                },
+                (None, Some(error)) => format!("❌ {}", markdown_cell(&error.to_string())),
+                (None, None) => String::new(),
+            };
+            let time = row
+                .timing
+                .map(|timing| format!("{:.2?}", timing.typical()))
+                .unwrap_or_default();
+            writeln!(self.0, "|{}|{phase}|{answer}|{time}|", row.day)?;
+        }
+        Ok(())
+    }
+}
+
+fn status(outcome: Result<Option<&Verdict>, &Error>) -> &'static str {
+    match outcome {
+        Ok(None) => "ok",
+        Ok(Some(Verdict::Correct)) => "✅",
+        Ok(Some(Verdict::Incorrect { .. })) => "wrong",
+        Err(Error::Panic { .. }) => "panicked",
+        Err(Error::TimedOut(_)) => "timed out",
+        Err(_) => "error",
+    }
+}
+
+/// A table of how every phase of every day went, followed by the failures and why.
+pub fn summary(results: &[DayResult]) -> String {
+    let mut table = format!("{:<5}{:<10}{:<10}{}\n", "Day", "Parse", "Part 1", "Part 2");
+    let mut failures = Vec::new();
+    for result in results {
+        let parse = status(result.parse.as_ref().map(|_| None));
+        let part = |part: Part| {
+            result
+                .parts
+                .iter()
+                .find(|result| result.part == part)
+                .map_or("-", |result| {
+                    status(result.outcome.as_ref().map(|_| result.verdict.as_ref()))
+                })
+        };
+        writeln!(
+            table,
+            "{:<5}{parse:<10}{:<10}{}",
+            result.day,
+            part(Part::One),
+            part(Part::Two)
+        )
+        .unwrap();
+        if let Err(err) = &result.parse {
+            failures.push(format!("day {} parse: {err}", result.day));
+        }
+        for part in &result.parts {
+            match (&part.outcome, &part.verdict) {
+                (Err(err), _) => {
+                    failures.push(format!("day {} part {}: {err}", result.day, part.part))
+                }
+                (Ok(answer), Some(Verdict::Incorrect { expected })) => failures.push(format!(
+                    "day {} part {}: answer {answer}, expected {expected}",
+                    result.day, part.part
+                )),
+                _ => {}
+            }
+        }
+    }
+    let failed = results.iter().filter(|result| result.failed()).count();
+    writeln!(
+        table,
+        "\n{} of {} days passed",
+        results.len() - failed,
+        results.len()
+    )
+    .unwrap();
+    for failure in failures {
+        writeln!(table, "  {failure}").unwrap();
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// A `Write` handle whose contents can be read back after the reporter is boxed.
+    #[derive(Clone, Default)]
+    struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn sample() -> DayResult {
+        DayResult {
+            day: 2,
+            parse: Ok(Timing::Once(Duration::from_micros(3))),
+            parse_allocs: None,
+            parts: vec![
+                PartResult {
+                    part: Part::One,
+                    outcome: Ok("8".to_string()),
+                    timing: Some(Timing::Once(Duration::from_nanos(1500))),
+                    allocs: Some(AllocStats {
+                        count: 12,
+                        bytes: 4096,
+                        peak: 4096,
+                    }),
+                    verdict: Some(Verdict::Correct),
+                },
+                PartResult {
+                    part: Part::Two,
+                    outcome: Err(Error::Solve("bad \"input\", sorry".to_string())),
+                    timing: None,
+                    allocs: None,
+                    verdict: None,
+                },
+            ],
+        }
+    }
+
+    fn render(format: Format, colour: bool) -> String {
+        let buffer = Buffer::default();
+        let mut reporter = format.reporter(buffer.clone(), colour);
+        reporter.start().unwrap();
+        reporter.day(&sample()).unwrap();
+        reporter.finish().unwrap();
+        let output = buffer.0.borrow().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn pretty_colour_test() {
+        assert!(render(Format::Pretty, true).contains(ANSI_GREEN_BOLD));
+        let output = render(Format::Pretty, false);
+        assert!(!output.contains('\x1B'));
+        assert!(output.contains("Part 1 Answer: \n8 ✅\n"));
+    }
+
+    #[test]
+    fn plain_test() {
+        assert_eq!(
+            render(Format::Plain, true),
+            "day 2 parse (3.00µs)\nday 2 part 1: 8 ✅ (1.50µs) [12 allocations, 4.0KiB allocated, 4.0KiB peak]\nday 2 part 2: error: bad \"input\", sorry\n"
+        );
+    }
+
+    #[test]
+    fn json_test() {
+        let output = render(Format::Json, false);
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines[0], r#"{"day":2,"phase":"parse","time_ns":3000}"#);
+        assert_eq!(
+            lines[1],
+            r#"{"day":2,"phase":"1","answer":"8","verified":true,"time_ns":1500,"allocations":12,"allocated_bytes":4096,"peak_bytes":4096}"#
+        );
+        assert_eq!(
+            lines[2],
+            r#"{"day":2,"phase":"2","error":"bad \"input\", sorry"}"#
+        );
+    }
+
+    #[test]
+    fn csv_test() {
+        let output = render(Format::Csv, false);
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[2], "2,1,8,true,,,1500,1,,,,,,12,4096,4096");
+        assert_eq!(lines[3], r#"2,2,,,,"bad ""input"", sorry",,,,,,,,,,"#);
+    }
+
+    #[test]
+    fn summary_test() {
+        let mut panicked = sample();
+        panicked.day = 4;
+        panicked.parts[1].outcome = Err(Error::Panic {
+            message: "index out of bounds".to_string(),
+            location: None,
+        });
+        panicked.parts.remove(0);
+        let summary = summary(&[sample(), panicked]);
+        let lines: Vec<_> = summary.lines().collect();
+        assert_eq!(lines[0], "Day  Parse     Part 1    Part 2");
+        assert_eq!(lines[1], "2    ok        ✅         error");
+        assert_eq!(lines[2], "4    ok        -         panicked");
+        assert_eq!(lines[4], "0 of 2 days passed");
+        assert_eq!(lines[5], "  day 2 part 2: bad \"input\", sorry");
+        assert_eq!(lines[6], "  day 4 part 2: panicked: index out of bounds");
+    }
+
+    #[test]
+    fn markdown_test() {
+        let output = render(Format::Markdown, false);
+        assert!(output.starts_with("|Day|Phase|Answer|Time|\n|---|---|---|---|\n"));
+        assert!(output.contains("|2|Part 1|8 ✅|1.50µs|\n"));
+    }
+}
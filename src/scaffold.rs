@@ -0,0 +1,178 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::manifest::{self, MANIFEST_PATH};
+
+/// The day module template, with `__DAY__` standing in for the day number.
+const TEMPLATE: &str = include_str!("template.rs");
+const PLACEHOLDER: &str = "__DAY__";
+const REGISTER_START: &str = "register! {\n";
+
+/// The source of a new day module, whose parts return placeholder answers.
+pub fn render(day: u8) -> String {
+    TEMPLATE.replace(PLACEHOLDER, &day.to_string())
+}
+
+/// Adds `day` to the `register!` list in `src/days/mod.rs`, keeping it in day order.
+pub fn register(mod_rs: &str, day: u8) -> Result<String, String> {
+    let start = mod_rs
+        .find(REGISTER_START)
+        .ok_or("could not find the register! list")?
+        + REGISTER_START.len();
+    let end = mod_rs[start..]
+        .find('}')
+        .ok_or("could not find the end of the register! list")?
+        + start;
+    let mut days = Vec::new();
+    for line in mod_rs[start..end].lines() {
+        let day = line
+            .trim()
+            .strip_prefix("day")
+            .and_then(|line| line.split_once(' '))
+            .and_then(|(number, _)| number.parse::<u8>().ok())
+            .ok_or(format!("unexpected line in the register! list: '{line}'"))?;
+        days.push(day);
+    }
+    if days.contains(&day) {
+        return Err(format!("day {day} is already registered"));
+    }
+    days.push(day);
+    days.sort();
+    let mut list = String::new();
+    for day in days {
+        writeln!(list, "    day{day} => Day{day},").unwrap();
+    }
+    Ok(format!("{}{list}{}", &mod_rs[..start], &mod_rs[end..]))
+}
+
+/// Creates `src/days/dayN.rs` and `example/dayN.txt` under `root`, registers the day and adds
+/// placeholder entries for it to the example manifest, unless it already lists the day. Refuses
+/// to touch a day whose module already exists. Returns the files written.
+pub fn create(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("day {day} is not between 1 and 25"));
+    }
+    let days_dir = root.join("src").join("days");
+    let module = days_dir.join(format!("day{day}.rs"));
+    if module.exists() {
+        return Err(format!(
+            "{} already exists, not overwriting it",
+            module.display()
+        ));
+    }
+    // A day scaffolded before and then removed keeps its entries, which mustn't be added twice
+    let manifest = root.join(MANIFEST_PATH);
+    let listed = match fs::read_to_string(&manifest) {
+        Ok(contents) => Some(contents),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(format!("could not read {}: {err}", manifest.display())),
+    };
+    let entries = manifest::parse(listed.as_deref().unwrap_or_default())
+        .map_err(|err| format!("{}: {err}", manifest.display()))?;
+    let listed_day = entries.iter().any(|entry| entry.day == day);
+    let mod_rs = days_dir.join("mod.rs");
+    let registered = fs::read_to_string(&mod_rs)
+        .map_err(|err| format!("could not read {}: {err}", mod_rs.display()))
+        .and_then(|contents| register(&contents, day))?;
+
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents)
+            .map_err(|err| format!("could not write {}: {err}", path.display()))
+    };
+    write(&module, &render(day))?;
+    write(&mod_rs, &registered)?;
+    let mut written = vec![module, mod_rs];
+    let example_dir = root.join("example");
+    let example = example_dir.join(format!("day{day}.txt"));
+    if !example.exists() {
+        fs::create_dir_all(&example_dir)
+            .map_err(|err| format!("could not create {}: {err}", example_dir.display()))?;
+        write(&example, "")?;
+        written.push(example);
+    }
+    if listed_day {
+        return Ok(written);
+    }
+    // The template's parts return 0, so the placeholders pass until the day is written
+    let mut entries = format!("{day} 1 day{day}.txt 0\n{day} 2 day{day}.txt 0\n");
+    // A hand-edited manifest may not end in a newline, which would join its last line to the
+    // first entry
+    if listed.is_some_and(|contents| !contents.is_empty() && !contents.ends_with('\n')) {
+        entries.insert(0, '\n');
+    }
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&manifest)
+        .and_then(|mut file| file.write_all(entries.as_bytes()))
+        .map_err(|err| format!("could not write {}: {err}", manifest.display()))?;
+    written.push(manifest);
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "use crate::Day;\n\nregister! {\n    day1 => Day1,\n    day3 => Day3,\n}\n\npub fn get() {}\n";
+
+    #[test]
+    fn render_test() {
+        let source = render(12);
+        assert!(source.contains("pub struct Day12;"));
+        assert!(source.contains("const DAY: u8 = 12;"));
+        assert!(!source.contains("#[cfg(test)]"));
+        assert!(!source.contains(PLACEHOLDER));
+    }
+
+    #[test]
+    fn register_test() {
+        assert_eq!(
+            register(MOD_RS, 2).unwrap(),
+            "use crate::Day;\n\nregister! {\n    day1 => Day1,\n    day2 => Day2,\n    day3 => Day3,\n}\n\npub fn get() {}\n"
+        );
+        assert!(register(MOD_RS, 3).is_err());
+        assert!(register("pub fn get() {}\n", 2).is_err());
+    }
+
+    #[test]
+    fn create_test() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src").join("days")).unwrap();
+        fs::write(root.join("src").join("days").join("mod.rs"), MOD_RS).unwrap();
+
+        let written = create(&root, 2).unwrap();
+        assert_eq!(written.len(), 4);
+        assert_eq!(
+            fs::read_to_string(root.join("src").join("days").join("day2.rs")).unwrap(),
+            render(2)
+        );
+        assert!(root.join("example").join("day2.txt").exists());
+        let entries =
+            crate::manifest::parse(&fs::read_to_string(root.join(MANIFEST_PATH)).unwrap());
+        assert_eq!(entries.unwrap().len(), 2);
+        assert!(create(&root, 2).unwrap_err().contains("already exists"));
+        assert!(create(&root, 26).is_err());
+
+        // A manifest without a trailing newline keeps its last entry intact
+        let manifest = fs::read_to_string(root.join(MANIFEST_PATH)).unwrap();
+        fs::write(root.join(MANIFEST_PATH), manifest.trim_end()).unwrap();
+        create(&root, 4).unwrap();
+        let entries =
+            crate::manifest::parse(&fs::read_to_string(root.join(MANIFEST_PATH)).unwrap());
+        assert_eq!(entries.unwrap().len(), 4);
+
+        // Removing a day's module leaves its entries, which scaffolding it again keeps as they are
+        fs::remove_file(root.join("src").join("days").join("day4.rs")).unwrap();
+        fs::write(root.join("src").join("days").join("mod.rs"), MOD_RS).unwrap();
+        let manifest = fs::read_to_string(root.join(MANIFEST_PATH)).unwrap();
+        assert_eq!(create(&root, 4).unwrap().len(), 2);
+        assert_eq!(
+            fs::read_to_string(root.join(MANIFEST_PATH)).unwrap(),
+            manifest
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,251 @@
+//! A byte-level cursor for parsers on the hot path. It reads integers, words and delimiters
+//! straight from the input's bytes without allocating, and its errors point into the input like
+//! those of the `str` helpers in [`crate::parse`].
+
+use std::any::type_name;
+use std::iter;
+
+use crate::math::{Integer, Signed};
+use crate::ParseError;
+
+/// A position in a puzzle input. Everything that reads from it skips spaces first, but not
+/// newlines, which [`Scanner::line`] splits on.
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    input: &'a str,
+    // Only ever moves past ASCII bytes or whole slices, so it's always a character boundary
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Scanner { input, pos: 0 }
+    }
+
+    /// What hasn't been read yet.
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    /// True if only spaces are left.
+    pub fn is_empty(&self) -> bool {
+        self.rest().bytes().all(|byte| byte == b' ')
+    }
+
+    fn skip_spaces(&mut self) {
+        let bytes = self.input.as_bytes();
+        while bytes.get(self.pos) == Some(&b' ') {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    /// Splits off the next line, without its line ending, or `None` at the end of the input.
+    pub fn line(&mut self) -> Option<Scanner<'a>> {
+        if self.pos == self.input.len() {
+            return None;
+        }
+        let rest = self.rest();
+        let (line, len) = match rest.as_bytes().iter().position(|&byte| byte == b'\n') {
+            Some(i) => (&rest[..i], i + 1),
+            None => (rest, rest.len()),
+        };
+        self.pos += len;
+        Some(Scanner::new(line.strip_suffix('\r').unwrap_or(line)))
+    }
+
+    /// The rest of the input split into lines, as with [`Scanner::line`].
+    pub fn lines(mut self) -> impl Iterator<Item = Scanner<'a>> {
+        iter::from_fn(move || self.line())
+    }
+
+    /// Reads `byte` if it comes next.
+    pub fn eat(&mut self, byte: u8) -> bool {
+        self.skip_spaces();
+        let found = self.peek() == Some(byte);
+        self.pos += found as usize;
+        found
+    }
+
+    /// Reads `tag`, or fails if something else comes next.
+    pub fn expect(&mut self, tag: &str) -> Result<(), ParseError> {
+        self.skip_spaces();
+        match self.rest().starts_with(tag) {
+            true => {
+                self.pos += tag.len();
+                Ok(())
+            }
+            false => Err(self.error(format!("'{tag}'"))),
+        }
+    }
+
+    /// Fails unless only spaces are left.
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_spaces();
+        match self.pos == self.input.len() {
+            true => Ok(()),
+            false => Err(self.error("end of line")),
+        }
+    }
+
+    /// Reads a run of ASCII letters and digits.
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        self.skip_spaces();
+        let start = self.pos;
+        let bytes = self.input.as_bytes();
+        while bytes.get(self.pos).is_some_and(u8::is_ascii_alphanumeric) {
+            self.pos += 1;
+        }
+        match start == self.pos {
+            true => Err(self.error("a word")),
+            false => Ok(&self.input[start..self.pos]),
+        }
+    }
+
+    /// Reads a number with no sign.
+    pub fn unsigned<T: Integer>(&mut self) -> Result<T, ParseError> {
+        self.skip_spaces();
+        self.digits(self.pos, false)
+    }
+
+    /// Reads a number with an optional `-`.
+    pub fn signed<T: Signed>(&mut self) -> Result<T, ParseError> {
+        self.skip_spaces();
+        let start = self.pos;
+        let negative = self.peek() == Some(b'-');
+        self.pos += negative as usize;
+        self.digits(start, negative)
+    }
+
+    /// Skips to the next digit, and the `-` before it if `T` is signed, then reads the number
+    /// there. `None` if there are no more digits.
+    pub fn next_integer<T: Integer>(&mut self) -> Option<Result<T, ParseError>> {
+        let bytes = self.input.as_bytes();
+        let offset = bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+        self.pos += offset;
+        let negative = T::try_from(-1).is_ok() && self.pos > 0 && bytes[self.pos - 1] == b'-';
+        Some(self.digits(self.pos - negative as usize, negative))
+    }
+
+    /// Reads the digits of a number that started at `start`, subtracting them from zero if it's
+    /// negative so the most negative value still fits.
+    fn digits<T: Integer>(&mut self, start: usize, negative: bool) -> Result<T, ParseError> {
+        let bytes = self.input.as_bytes();
+        let digits_start = self.pos;
+        let mut value = Some(T::ZERO);
+        while let Some(&byte) = bytes.get(self.pos).filter(|byte| byte.is_ascii_digit()) {
+            // Keeps reading after an overflow, so the error shows the whole number
+            value = value.and_then(|value| push_digit(value, byte - b'0', negative));
+            self.pos += 1;
+        }
+        if self.pos == digits_start {
+            self.pos = start;
+            return Err(self.error("a number"));
+        }
+        let span = &self.input[start..self.pos];
+        value.ok_or_else(|| {
+            ParseError::new(span, format!("a number that fits in {}", type_name::<T>()))
+        })
+    }
+
+    /// An error at the current position, showing the rest of the word there.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let rest = self.rest();
+        let len = rest
+            .bytes()
+            .position(|byte| byte.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        ParseError::new(&rest[..len], expected)
+    }
+}
+
+/// `value` with `digit` appended, or `None` if it doesn't fit.
+fn push_digit<T: Integer>(value: T, digit: u8, negative: bool) -> Option<T> {
+    let shifted = value.checked_mul(T::try_from(10).ok()?)?;
+    let digit = T::try_from(digit as i128).ok()?;
+    match negative {
+        true => shifted.checked_sub(digit),
+        false => shifted.checked_add(digit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_test() {
+        let mut scanner = Scanner::new("Game 12: 3 blue, -4 red");
+        scanner.expect("Game").unwrap();
+        assert_eq!(scanner.unsigned::<u32>(), Ok(12));
+        assert!(scanner.eat(b':'));
+        assert!(!scanner.eat(b':'));
+        assert_eq!(scanner.unsigned::<u8>(), Ok(3));
+        assert_eq!(scanner.word(), Ok("blue"));
+        assert!(scanner.eat(b','));
+        assert_eq!(scanner.signed::<i64>(), Ok(-4));
+        assert_eq!(scanner.rest(), " red");
+        assert!(!scanner.is_empty());
+        assert_eq!(scanner.word(), Ok("red"));
+        assert!(scanner.is_empty());
+        assert_eq!(scanner.end(), Ok(()));
+        assert_eq!(Scanner::new("-128").signed::<i8>(), Ok(-128));
+        assert_eq!(Scanner::new("0042").unsigned::<u64>(), Ok(42));
+    }
+
+    #[test]
+    fn line_test() {
+        let lines: Vec<&str> = Scanner::new("a b\r\n\nc")
+            .lines()
+            .map(|line| line.rest())
+            .collect();
+        assert_eq!(lines, ["a b", "", "c"]);
+        assert!(Scanner::new("").line().is_none());
+    }
+
+    #[test]
+    fn next_integer_test() {
+        let mut scanner = Scanner::new("x=-12, y=5..-7");
+        let signed: Vec<i32> = std::iter::from_fn(|| scanner.next_integer())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(signed, [-12, 5, -7]);
+        let mut scanner = Scanner::new("x=-12, y=5..-7");
+        let unsigned: Vec<u32> = std::iter::from_fn(|| scanner.next_integer())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(unsigned, [12, 5, 7]);
+    }
+
+    #[test]
+    fn error_test() {
+        let input = "Game 1: 3 blue\nGame 2: 300 purple";
+        let mut scanner = Scanner::new(input);
+        scanner.line();
+        let mut line = scanner.line().unwrap();
+        line.expect("Game").unwrap();
+        let err = line.expect(":").unwrap_err().locate(input);
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 6: expected ':', found '2:'"
+        );
+        line.unsigned::<u32>().unwrap();
+        line.eat(b':');
+        let err = line.unsigned::<u8>().unwrap_err().locate(input);
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 9: expected a number that fits in u8, found '300'"
+        );
+        let err = line.unsigned::<u8>().unwrap_err().locate(input);
+        assert_eq!(err.expected, "a number");
+        assert_eq!(err.found, "'purple'");
+        line.word().unwrap();
+        assert_eq!(line.end(), Ok(()));
+        assert_eq!(line.word().unwrap_err().found, "end of line");
+        assert_eq!(Scanner::new("-").signed::<i8>().unwrap_err().found, "'-'");
+        assert!(Scanner::new("-129").signed::<i8>().is_err());
+    }
+}
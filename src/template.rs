@@ -1,27 +1,22 @@
-fn part1(input: &str) -> i32 {
-    0
-}
+use crate::{Error, ParseError, Solution};
 
-fn part2(input: &str) -> i32 {
-    0
-}
+pub struct Day__DAY__;
 
-fn main() {
-    let input = include_str!("../input/day9.txt");
-    aoc2023::solve_puzzles(input, part1, part2)
-}
+impl Solution for Day__DAY__ {
+    const DAY: u8 = __DAY__;
+    type Parsed<'a> = &'a str;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const EXAMPLE: &str = include_str!("../example/day9.txt");
-    #[test]
-    fn part_1_test() {
-        assert_eq!(part1(EXAMPLE), 0);
+    fn part1(_input: &&str) -> Result<i32, Error> {
+        Ok(0)
     }
 
-    #[test]
-    fn part_2_test() {
-        assert_eq!(part2(EXAMPLE), 0);
+    fn part2(_input: &&str) -> Result<i32, Error> {
+        Ok(0)
     }
 }
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::input;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The files that trigger a re-run of `day`: its input, examples and source.
+pub fn watched_paths(day: u8) -> Vec<PathBuf> {
+    let mut paths = vec![
+        input::default_path(day),
+        PathBuf::from(format!("src/days/day{day}.rs")),
+    ];
+    if let Ok(entries) = std::fs::read_dir("example") {
+        let mut examples: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| is_example(path, day))
+            .collect();
+        examples.sort();
+        examples.retain(|example| !paths.contains(example));
+        paths.extend(examples);
+    }
+    paths
+}
+
+/// True for `dayN.txt` and `dayN_*.txt`, but not another day that starts with the same digit.
+fn is_example(path: &Path, day: u8) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    let Some(rest) = name.strip_prefix(&format!("day{day}")) else {
+        return false;
+    };
+    rest == ".txt" || (rest.starts_with('_') && rest.ends_with(".txt"))
+}
+
+fn modified(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            std::fs::metadata(path)
+                .and_then(|meta| meta.modified())
+                .ok()
+        })
+        .collect()
+}
+
+/// One phase of a run, read back from the CSV output of `aoc <day> --format csv`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phase {
+    pub phase: String,
+    pub answer: String,
+    pub error: String,
+    pub time: Option<Duration>,
+}
+
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(String::new()),
+            (c, _) => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+/// Reads the phases out of CSV report output, skipping the header.
+pub fn parse_run(csv: &str) -> Vec<Phase> {
+    csv.lines()
+        .skip(1)
+        .map(csv_fields)
+        .filter(|fields| fields.len() > 6)
+        .map(|fields| Phase {
+            phase: fields[1].clone(),
+            answer: fields[2].clone(),
+            error: fields[5].clone(),
+            time: fields[6].parse().ok().map(Duration::from_nanos),
+        })
+        .collect()
+}
+
+fn label(phase: &str) -> String {
+    match phase {
+        "parse" => "Parse".to_string(),
+        part => format!("Part {part}"),
+    }
+}
+
+/// The answer or error a phase ended with, if it has either.
+fn outcome(phase: &Phase) -> Option<String> {
+    match (phase.answer.as_str(), phase.error.as_str()) {
+        ("", "") => None,
+        ("", error) => Some(format!("error: {error}")),
+        (answer, _) => Some(answer.to_string()),
+    }
+}
+
+/// Describes each phase of `current`, and how it changed since `previous` if there was one.
+pub fn diff(previous: Option<&[Phase]>, current: &[Phase]) -> String {
+    let previous: HashMap<_, _> = previous
+        .unwrap_or_default()
+        .iter()
+        .map(|phase| (phase.phase.as_str(), phase))
+        .collect();
+    let mut out = String::new();
+    for phase in current {
+        let before = previous.get(phase.phase.as_str());
+        let mut line = label(&phase.phase);
+        match (before.and_then(|before| outcome(before)), outcome(phase)) {
+            (Some(old), Some(new)) if old != new => write!(line, ": {old} -> {new}").unwrap(),
+            (Some(_), Some(new)) => write!(line, ": {new} (unchanged)").unwrap(),
+            (None, Some(new)) => write!(line, ": {new}").unwrap(),
+            (_, None) => {}
+        }
+        match (before.and_then(|before| before.time), phase.time) {
+            (Some(old), Some(new)) => write!(
+                line,
+                ", {:.2?} -> {:.2?} ({:+.1}%)",
+                old,
+                new,
+                (new.as_secs_f64() / old.as_secs_f64() - 1.0) * 100.0
+            )
+            .unwrap(),
+            (None, Some(new)) => write!(line, ", {new:.2?}").unwrap(),
+            _ => {}
+        }
+        writeln!(out, "{line}").unwrap();
+    }
+    out
+}
+
+fn cargo(args: &[&str]) -> std::io::Result<std::process::Output> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    Command::new(cargo).args(args).output()
+}
+
+/// Runs the day's example tests and the day itself, returning the phases if the run worked.
+fn run_once(day: u8, previous: Option<&[Phase]>) -> Option<Vec<Phase>> {
+    let unit_tests = format!("days::day{day}::");
+    let example_tests = format!("examples::generated::day{day}_");
+    match cargo(&[
+        "test",
+        "--quiet",
+        "--lib",
+        "--",
+        &unit_tests,
+        &example_tests,
+    ]) {
+        Ok(output) if output.status.success() => eprintln!("Example tests passed"),
+        Ok(output) => {
+            eprintln!("Example tests failed:");
+            eprint!("{}", String::from_utf8_lossy(&output.stdout));
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        }
+        Err(err) => eprintln!("could not run cargo test: {err}"),
+    }
+    let day = day.to_string();
+    let args = ["run", "--quiet", "--release", "--", &day, "--format", "csv"];
+    match cargo(&args) {
+        Ok(output) => {
+            let phases = parse_run(&String::from_utf8_lossy(&output.stdout));
+            if phases.is_empty() {
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                return None;
+            }
+            eprint!("{}", diff(previous, &phases));
+            Some(phases)
+        }
+        Err(err) => {
+            eprintln!("could not run cargo run: {err}");
+            None
+        }
+    }
+}
+
+/// Re-runs `day` whenever one of its watched files changes. Never returns.
+pub fn watch(day: u8) -> ! {
+    let mut previous: Option<Vec<Phase>> = None;
+    let mut seen = None;
+    loop {
+        // The example list is re-read so new example files are picked up
+        let paths = watched_paths(day);
+        let stamps = modified(&paths);
+        if seen.as_ref() != Some(&stamps) {
+            if seen.is_none() {
+                eprintln!("Watching:");
+                for path in &paths {
+                    eprintln!("  {}", path.display());
+                }
+            }
+            eprintln!("\nRunning day {day}");
+            if let Some(phases) = run_once(day, previous.as_deref()) {
+                previous = Some(phases);
+            }
+            // From before the run, so anything changed during it triggers another
+            seen = Some(stamps);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_example_test() {
+        assert!(is_example(Path::new("example/day1.txt"), 1));
+        assert!(is_example(Path::new("example/day1_pt2.txt"), 1));
+        assert!(!is_example(Path::new("example/day10.txt"), 1));
+        assert!(!is_example(Path::new("example/day1.rs"), 1));
+        assert!(watched_paths(8).contains(&PathBuf::from("example/day8_3.txt")));
+    }
+
+    const RUN: &str = "day,phase,answer,verified,expected,error,time_ns,runs,min_ns,median_ns,mean_ns,stddev_ns,p95_ns,allocations,allocated_bytes,peak_bytes
+9,parse,,,,,3000,1,,,,,,,,
+9,1,114,,,,2000,1,,,,,,,,
+9,2,,,,\"expected a number, found 'x'\",,,,,,,,,,
+";
+
+    #[test]
+    fn parse_run_test() {
+        let phases = parse_run(RUN);
+        assert_eq!(phases.len(), 3);
+        assert_eq!(phases[1].answer, "114");
+        assert_eq!(phases[1].time, Some(Duration::from_micros(2)));
+        assert_eq!(phases[2].error, "expected a number, found 'x'");
+        assert_eq!(phases[2].time, None);
+    }
+
+    #[test]
+    fn diff_test() {
+        let before = parse_run(RUN);
+        let mut after = before.clone();
+        after[1].answer = "115".to_string();
+        after[1].time = Some(Duration::from_micros(1));
+        after[2].answer = "2".to_string();
+        after[2].error = String::new();
+        after[2].time = Some(Duration::from_micros(4));
+        assert_eq!(
+            diff(Some(&before), &after),
+            "Parse, 3.00µs -> 3.00µs (+0.0%)\n\
+             Part 1: 114 -> 115, 2.00µs -> 1.00µs (-50.0%)\n\
+             Part 2: error: expected a number, found 'x' -> 2, 4.00µs\n"
+        );
+        assert_eq!(
+            diff(Some(&after), &after).lines().nth(1),
+            Some("Part 1: 115 (unchanged), 1.00µs -> 1.00µs (+0.0%)")
+        );
+        assert_eq!(
+            diff(None, &before).lines().nth(1),
+            Some("Part 1: 114, 2.00µs")
+        );
+    }
+}